@@ -0,0 +1,54 @@
+//! Lexer benchmark.
+//!
+//! A plain timing loop over a repeated program, kept free of benchmark
+//! dependencies. Run with `cargo bench --bench lexer`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use monkey::lexer::Lexer;
+use monkey::token::TokenValue;
+
+const PROGRAM: &str = r#"let five = 5;
+let ten = 10;
+
+let add = fn(x, y) {
+  x + y;
+};
+
+let result = add(five, ten);
+!-/*5;
+5 < 10 > 4;
+if (5 < 10) {
+    return true;
+} else {
+    return false;
+}
+10 == 10;
+10 != 9;
+"#;
+
+const ITERATIONS: usize = 200;
+
+fn main() {
+    let input = PROGRAM.repeat(500);
+
+    let start = Instant::now();
+    let mut tokens = 0usize;
+    for _ in 0..ITERATIONS {
+        let mut lexer = Lexer::from_text(black_box(&input));
+        while let Some(tok) = lexer.next_token() {
+            if tok.value == TokenValue::Eof {
+                break;
+            }
+            tokens += 1;
+            black_box(tok);
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "lexed {tokens} tokens in {elapsed:?} ({:.0} tokens/sec)",
+        tokens as f64 / elapsed.as_secs_f64()
+    );
+}
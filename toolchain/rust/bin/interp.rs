@@ -1,10 +1,46 @@
 //! Monkey interpreter.
 
+use std::env;
+use std::fs;
 use std::io;
+use std::process;
 
+use monkey::lexer::Lexer;
+use monkey::parser::Parser;
 use monkey::repl;
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--version") {
+        println!("monkey {}", monkey::version());
+        return;
+    }
+
+    if let Some(pos) = args.iter().position(|arg| arg == "--check") {
+        let Some(path) = args.get(pos + 1) else {
+            eprintln!("usage: monkey --check <file>");
+            process::exit(2);
+        };
+        let source = fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("{path}: {err}");
+            process::exit(2);
+        });
+        let mut parser = Parser::new(Lexer::from_text(&source));
+        let program = parser.parse();
+        for error in parser.errors() {
+            eprintln!("{path}: error: {error}");
+        }
+        for warning in parser.warnings() {
+            eprintln!("{path}: warning: {warning}");
+        }
+        let summary = parser.summary(&program);
+        println!("{summary}");
+        if summary.error_count > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
     println!("Welcome to the Monkey programming language!");
     repl::start(io::stdout()).expect("failed to readline");
 }
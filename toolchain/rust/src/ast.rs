@@ -1,18 +1,72 @@
 //! # AST
 
+mod json;
 pub(super) mod syntax;
 
+use std::fmt;
+
 use self::syntax::*;
 
+/// `Program` is the root node of the AST.
 #[derive(Debug)]
 pub struct Program {
     /// A program is a sequence of statements.
     pub statements: Vec<Statement>,
 }
 
+/// `Statement` represents a statement of the program.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Statement {
+    /// A variable declaration: `let x = 5;`.
     Var(LocalVarDecl),
+    /// A return statement: `return x;`.
     Return(ReturnStatement),
+    /// An expression statement: `x + 1;`.
     Expr(ExprStatement),
+    /// A block statement: `{ let x = 1; }`.
+    Block(BlockStatement),
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stmt in &self.statements {
+            write!(f, "{stmt}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var(LocalVarDecl {
+                name,
+                annotation,
+                expr,
+                ..
+            }) => {
+                write!(f, "let {}", name.as_str())?;
+                if let Some(annotation) = annotation {
+                    write!(f, ": {annotation}")?;
+                }
+                write!(f, " = {expr};")
+            }
+            Self::Return(ReturnStatement {
+                expr: Some(expr), ..
+            }) => write!(f, "return {expr};"),
+            Self::Return(ReturnStatement { expr: None, .. }) => write!(f, "return;"),
+            Self::Expr(ExprStatement { expr, .. }) => write!(f, "{expr}"),
+            Self::Block(block) => write!(f, "{block}"),
+        }
+    }
+}
+
+impl fmt::Display for BlockStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{ ")?;
+        for stmt in &self.statements {
+            write!(f, "{stmt} ")?;
+        }
+        write!(f, "}}")
+    }
 }
@@ -0,0 +1,211 @@
+//! This module implements the JSON export of the AST.
+
+use std::fmt::Write;
+
+use super::syntax::*;
+use super::{Program, Statement};
+use crate::expr::{ExprData, Pattern};
+use crate::span::Span;
+use crate::token::Token;
+
+impl Program {
+    /// Returns a JSON representation of the program.
+    pub fn to_json(&self) -> String {
+        let statements: Vec<String> = self.statements.iter().map(statement).collect();
+        format!(
+            r#"{{"type":"Program","statements":[{}]}}"#,
+            statements.join(",")
+        )
+    }
+}
+
+/// Returns the JSON representation of a statement.
+fn statement(stmt: &Statement) -> String {
+    match stmt {
+        Statement::Var(LocalVarDecl {
+            token,
+            name,
+            annotation,
+            expr,
+            ..
+        }) => {
+            let annotation = annotation
+                .map(|annotation| format!(r#","annotation":{}"#, string(annotation.as_str())))
+                .unwrap_or_default();
+            format!(
+                r#"{{"type":"LetStatement","span":{},"name":{}{},"value":{}}}"#,
+                span(&token.span),
+                identifier(name),
+                annotation,
+                expression(expr)
+            )
+        }
+        Statement::Return(ReturnStatement { token, expr }) => format!(
+            r#"{{"type":"ReturnStatement","span":{},"value":{}}}"#,
+            span(&token.span),
+            expr.as_ref().map_or_else(|| "null".to_string(), expression)
+        ),
+        Statement::Expr(ExprStatement { token, expr }) => format!(
+            r#"{{"type":"ExpressionStatement","span":{},"value":{}}}"#,
+            span(&token.span),
+            expression(expr)
+        ),
+        Statement::Block(BlockStatement { token, statements }) => {
+            let statements: Vec<String> = statements.iter().map(statement).collect();
+            format!(
+                r#"{{"type":"BlockStatement","span":{},"statements":[{}]}}"#,
+                span(&token.span),
+                statements.join(",")
+            )
+        }
+    }
+}
+
+/// Returns the JSON representation of an expression.
+fn expression(expr: &ExprData) -> String {
+    match expr {
+        ExprData::Ident(value) => format!(r#"{{"type":"Identifier","value":{}}}"#, string(value)),
+        ExprData::Integer(value) => format!(r#"{{"type":"IntegerLiteral","value":{value}}}"#),
+        ExprData::Boolean(value) => format!(r#"{{"type":"BooleanLiteral","value":{value}}}"#),
+        ExprData::Prefix { operator, right } => format!(
+            r#"{{"type":"PrefixExpression","operator":{},"right":{}}}"#,
+            string(operator.as_str()),
+            expression(right)
+        ),
+        ExprData::Infix {
+            left,
+            operator,
+            right,
+        } => format!(
+            r#"{{"type":"InfixExpression","left":{},"operator":{},"right":{}}}"#,
+            expression(left),
+            string(operator.as_str()),
+            expression(right)
+        ),
+        ExprData::Conditional {
+            condition,
+            consequence,
+            alternative,
+        } => format!(
+            r#"{{"type":"ConditionalExpression","condition":{},"consequence":{},"alternative":{}}}"#,
+            expression(condition),
+            expression(consequence),
+            expression(alternative)
+        ),
+        ExprData::Match { scrutinee, arms } => {
+            let arms: Vec<String> = arms
+                .iter()
+                .map(|arm| {
+                    let pattern = match &arm.pattern {
+                        Pattern::Literal(expr) => expression(expr),
+                        Pattern::Wildcard => r#"{"type":"Wildcard"}"#.to_string(),
+                    };
+                    format!(
+                        r#"{{"type":"MatchArm","pattern":{},"body":{}}}"#,
+                        pattern,
+                        expression(&arm.body)
+                    )
+                })
+                .collect();
+            format!(
+                r#"{{"type":"MatchExpression","scrutinee":{},"arms":[{}]}}"#,
+                expression(scrutinee),
+                arms.join(",")
+            )
+        }
+        ExprData::Function { parameters, body } => {
+            let parameters: Vec<String> = parameters.iter().map(|name| string(name)).collect();
+            let statements: Vec<String> = body.statements.iter().map(statement).collect();
+            format!(
+                r#"{{"type":"FunctionLiteral","parameters":[{}],"body":[{}]}}"#,
+                parameters.join(","),
+                statements.join(",")
+            )
+        }
+        ExprData::Sequence(exprs) => {
+            let exprs: Vec<String> = exprs.iter().map(expression).collect();
+            format!(
+                r#"{{"type":"SequenceExpression","expressions":[{}]}}"#,
+                exprs.join(",")
+            )
+        }
+        ExprData::Call {
+            function,
+            arguments,
+        } => {
+            let arguments: Vec<String> = arguments.iter().map(expression).collect();
+            format!(
+                r#"{{"type":"CallExpression","function":{},"arguments":[{}]}}"#,
+                expression(function),
+                arguments.join(",")
+            )
+        }
+    }
+}
+
+/// Returns the JSON representation of an identifier token.
+fn identifier(token: &Token) -> String {
+    format!(
+        r#"{{"type":"Identifier","value":{},"span":{}}}"#,
+        string(&token.as_str()),
+        span(&token.span)
+    )
+}
+
+/// Returns the JSON representation of a span.
+fn span(span: &Span) -> String {
+    format!(r#"{{"line":{},"column":{}}}"#, span.lineno, span.column_pos)
+}
+
+/// Returns the value as a JSON string literal.
+fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_str;
+
+    #[test]
+    fn program_to_json() {
+        let (program, errors) = parse_str("let x = 5;\nx + 1;");
+        assert!(errors.is_empty(), "{errors:?}");
+
+        assert_eq!(
+            program.to_json(),
+            r#"{"type":"Program","statements":[{"type":"LetStatement","span":{"line":1,"column":0},"name":{"type":"Identifier","value":"x","span":{"line":1,"column":4}},"value":{"type":"IntegerLiteral","value":5}},{"type":"ExpressionStatement","span":{"line":2,"column":0},"value":{"type":"InfixExpression","left":{"type":"Identifier","value":"x"},"operator":"+","right":{"type":"IntegerLiteral","value":1}}}]}"#
+        );
+    }
+
+    #[test]
+    fn annotated_let_to_json() {
+        let (program, errors) = parse_str("let x: int = 5;");
+        assert!(errors.is_empty(), "{errors:?}");
+
+        assert_eq!(
+            program.to_json(),
+            r#"{"type":"Program","statements":[{"type":"LetStatement","span":{"line":1,"column":0},"name":{"type":"Identifier","value":"x","span":{"line":1,"column":4}},"annotation":"int","value":{"type":"IntegerLiteral","value":5}}]}"#
+        );
+    }
+
+    #[test]
+    fn escape_json_string() {
+        assert_eq!(super::string("a\"b\\c\n"), r#""a\"b\\c\n""#);
+    }
+}
@@ -1,6 +1,10 @@
 //! This module defines the data structure representing code syntax.
 
+use std::fmt;
+
+use crate::ast::Statement;
 use crate::expr::ExprData;
+use crate::span::Span;
 use crate::token::Token;
 
 /// `LocalVardecl` represents a variable declaration.
@@ -10,8 +14,70 @@ pub struct LocalVarDecl {
     pub token: Token,
     /// `Name` is the name of the identifier.
     pub name: Token,
+    /// The optional type annotation: `let x: int = 5;`.
+    pub annotation: Option<TypeAnnotation>,
     /// This is the expression value.
     pub expr: ExprData,
+    /// The last token of the declaration.
+    pub end: Token,
+}
+
+impl LocalVarDecl {
+    /// Returns the span of the whole declaration, from `let` through the
+    /// terminating `;` when present.
+    pub const fn span(&self) -> Span {
+        self.token.span.merge(self.end.span)
+    }
+}
+
+/// `TypeAnnotation` represents the type named in a `let` annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAnnotation {
+    /// `int`
+    Int,
+    /// `bool`
+    Bool,
+    /// `string`
+    String,
+    /// `array`
+    Array,
+    /// `hash`
+    Hash,
+    /// `fn`
+    Function,
+}
+
+impl TypeAnnotation {
+    /// Returns the annotation with the given name, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(Self::Int),
+            "bool" => Some(Self::Bool),
+            "string" => Some(Self::String),
+            "array" => Some(Self::Array),
+            "hash" => Some(Self::Hash),
+            "fn" => Some(Self::Function),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the annotation.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Bool => "bool",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Hash => "hash",
+            Self::Function => "fn",
+        }
+    }
+}
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// `ReturnStatement` represents a return statement.
@@ -19,8 +85,8 @@ pub struct LocalVarDecl {
 pub struct ReturnStatement {
     /// `return` token.
     pub token: Token,
-    /// returned expresssion
-    pub expr: ExprData,
+    /// returned expresssion, `None` for a bare `return;`
+    pub expr: Option<ExprData>,
 }
 
 /// `ExprStatement` represents an expression statement.
@@ -31,3 +97,12 @@ pub struct ExprStatement {
     /// The expression value
     pub expr: ExprData,
 }
+
+/// `BlockStatement` represents a block of statements.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BlockStatement {
+    /// `{` token.
+    pub token: Token,
+    /// The statements in the block.
+    pub statements: Vec<Statement>,
+}
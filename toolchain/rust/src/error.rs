@@ -2,7 +2,9 @@
 
 use std::error::Error as StdError;
 use std::fmt;
+use std::io;
 
+use crate::span::Span;
 use crate::token::TokenKind;
 
 /// Error type.
@@ -10,9 +12,126 @@ use crate::token::TokenKind;
 pub enum Error {
     /// The error type when an unexpected token is encountered.
     SyntaxError {
+        /// The expected token kind.
         expected: TokenKind,
+        /// The token kind found instead.
         found: TokenKind,
+        /// The span of the token found.
+        span: Span,
     },
+
+    /// The error type when an `else` is found without a preceding `if`.
+    ElseWithoutIf {
+        /// The span of the `else` keyword.
+        span: Span,
+    },
+
+    /// The error type when a token cannot start an expression.
+    NoPrefixParse {
+        /// The token kind found.
+        found: TokenKind,
+        /// The span of the token.
+        span: Span,
+    },
+
+    /// The error type when an operator is found where an expression is expected.
+    ExpectedExpression {
+        /// The operator kind found.
+        found: TokenKind,
+        /// The span of the operator.
+        span: Span,
+    },
+
+    /// The error type when an integer literal cannot be represented.
+    InvalidInteger {
+        /// The integer literal.
+        literal: String,
+        /// The span of the literal.
+        span: Span,
+    },
+
+    /// The error type when a keyword is found where an identifier is expected.
+    KeywordAsIdentifier {
+        /// The keyword found.
+        keyword: TokenKind,
+        /// The span of the keyword.
+        span: Span,
+    },
+
+    /// The error type when a function declares the same parameter twice.
+    DuplicateParameter {
+        /// The parameter name.
+        name: String,
+        /// The span of the duplicate parameter.
+        span: Span,
+    },
+
+    /// The error type when the input is not valid UTF-8.
+    InvalidUtf8 {
+        /// The span of the invalid bytes.
+        span: Span,
+    },
+
+    /// The error type when the input cannot be read.
+    Io {
+        /// The read error.
+        error: io::Error,
+        /// The position where reading stopped.
+        span: Span,
+    },
+
+    /// The error type when a match arm pattern is not a literal or `_`.
+    InvalidPattern {
+        /// The pattern found.
+        pattern: String,
+        /// The span of the pattern.
+        span: Span,
+    },
+
+    /// The error type when a `let` annotation names an unknown type.
+    UnknownTypeAnnotation {
+        /// The annotation found.
+        name: String,
+        /// The span of the annotation.
+        span: Span,
+    },
+
+    /// The error type when a number is immediately followed by identifier characters.
+    InvalidNumericLiteral {
+        /// The whole run of digits and identifier characters.
+        literal: String,
+        /// The span of the run.
+        span: Span,
+    },
+
+    /// The error type when the lexer finds an illegal character in strict mode.
+    IllegalCharacter {
+        /// The illegal character.
+        ch: char,
+        /// The span of the character.
+        span: Span,
+    },
+}
+
+impl Error {
+    /// Returns the location of the error.
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::SyntaxError { span, .. }
+            | Self::ElseWithoutIf { span }
+            | Self::NoPrefixParse { span, .. }
+            | Self::InvalidInteger { span, .. }
+            | Self::KeywordAsIdentifier { span, .. }
+            | Self::ExpectedExpression { span, .. }
+            | Self::DuplicateParameter { span, .. }
+            | Self::InvalidUtf8 { span }
+            | Self::Io { span, .. }
+            | Self::InvalidPattern { span, .. }
+            | Self::UnknownTypeAnnotation { span, .. }
+            | Self::InvalidNumericLiteral { span, .. }
+            | Self::IllegalCharacter { span, .. } => *span,
+        }
+    }
 }
 
 impl StdError for Error {}
@@ -20,10 +139,107 @@ impl StdError for Error {}
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match self {
-            Self::SyntaxError { expected, found } => {
-                format!("unexpected : '{expected}\nfound: '{found}'")
+            Self::SyntaxError {
+                expected,
+                found,
+                span,
+            } => {
+                format!("expected '{expected}', found '{found}' at {span}")
+            }
+            Self::ElseWithoutIf { .. } => "'else' without matching 'if'".to_string(),
+            Self::NoPrefixParse { found, .. } => {
+                format!("no prefix parse function for '{found}' found")
+            }
+            Self::InvalidInteger { literal, .. } => {
+                format!("could not parse '{literal}' as integer")
+            }
+            Self::ExpectedExpression { found, .. } => {
+                format!("expected expression, found '{found}'")
+            }
+            Self::KeywordAsIdentifier { keyword, .. } => {
+                format!("expected identifier, found keyword '{keyword}'")
+            }
+            Self::DuplicateParameter { name, .. } => format!("duplicate parameter name: {name}"),
+            Self::InvalidUtf8 { span } => format!("invalid UTF-8 at {span}"),
+            Self::Io { error, span } => format!("could not read input at {span}: {error}"),
+            Self::InvalidPattern { pattern, .. } => format!("invalid match pattern: {pattern}"),
+            Self::UnknownTypeAnnotation { name, span } => {
+                format!("unknown type annotation '{name}' at {span}")
             }
+            Self::InvalidNumericLiteral { literal, span } => {
+                format!("invalid numeric literal '{literal}' at {span}")
+            }
+            Self::IllegalCharacter { ch, span } => format!("illegal character '{ch}' at {span}"),
         };
         write!(f, "{}", value)
     }
 }
+
+/// Warning type.
+///
+/// Warnings report suspicious code that still parses.
+#[derive(Debug)]
+pub enum Warning {
+    /// The warning type when a binding shadows another one in the same scope.
+    ShadowedBinding {
+        /// The binding name.
+        name: String,
+        /// The span of the shadowing binding.
+        span: Span,
+    },
+    /// The warning type when statements follow a `return` in the same block.
+    UnreachableCode {
+        /// The span of the first unreachable statement.
+        span: Span,
+    },
+    /// The warning type when a statement does not end with `;`.
+    ///
+    /// It is only reported when [`ParserConfig::strict_semicolons`] is set.
+    ///
+    /// [`ParserConfig::strict_semicolons`]: crate::parser::ParserConfig::strict_semicolons
+    MissingSemicolon {
+        /// The span of the token found where the `;` was expected.
+        span: Span,
+    },
+}
+
+impl Warning {
+    /// Returns the location of the warning.
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::ShadowedBinding { span, .. }
+            | Self::UnreachableCode { span }
+            | Self::MissingSemicolon { span } => *span,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShadowedBinding { name, span } => {
+                write!(f, "'{name}' at {span} shadows an earlier binding")
+            }
+            Self::UnreachableCode { span } => write!(f, "unreachable statement at {span}"),
+            Self::MissingSemicolon { span } => write!(f, "expected ';' at {span}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_span() {
+        let span = Span::new(2, 7).with_len(1);
+        let error = Error::SyntaxError {
+            expected: TokenKind::Eq,
+            found: TokenKind::Number,
+            span,
+        };
+        assert_eq!(error.span(), span);
+        assert_eq!(error.to_string(), "expected '=', found 'number' at 2:7");
+        assert_eq!(Error::ElseWithoutIf { span }.span(), span);
+    }
+}
@@ -1,9 +1,165 @@
 //! This module defines the data structures for an expressions.
 
+use std::fmt;
+
+use crate::ast::syntax::BlockStatement;
+use crate::token::TokenKind;
+
 /// `ExprData` represents an expression data.
 #[derive(Debug, PartialEq, Eq)]
 pub enum ExprData {
-    VariableDecl(String),
-    Return(String),
-    ExprStatement(String),
+    /// An identifier: `foobar`.
+    Ident(String),
+    /// An integer literal: `5`.
+    Integer(i64),
+    /// A boolean literal: `true` or `false`.
+    Boolean(bool),
+    /// A prefix expression: `-5` or `!ok`.
+    Prefix {
+        /// The prefix operator.
+        operator: TokenKind,
+        /// The operand.
+        right: Box<ExprData>,
+    },
+    /// An infix expression: `5 + 5`.
+    Infix {
+        /// The left operand.
+        left: Box<ExprData>,
+        /// The infix operator.
+        operator: TokenKind,
+        /// The right operand.
+        right: Box<ExprData>,
+    },
+    /// A conditional expression: `c ? a : b`.
+    Conditional {
+        /// The condition.
+        condition: Box<ExprData>,
+        /// The expression evaluated when the condition is truthy.
+        consequence: Box<ExprData>,
+        /// The expression evaluated otherwise.
+        alternative: Box<ExprData>,
+    },
+    /// A match expression: `match x { 1 => "one", _ => "other" }`.
+    ///
+    /// The scrutinee is compared against each arm's pattern in order and the
+    /// first matching arm is selected. A match without a matching arm yields
+    /// `null`.
+    Match {
+        /// The matched expression.
+        scrutinee: Box<ExprData>,
+        /// The arms of the match.
+        arms: Vec<MatchArm>,
+    },
+    /// A function literal: `fn(x, y) { x + y }`.
+    Function {
+        /// The parameter names.
+        parameters: Vec<String>,
+        /// The function body.
+        body: BlockStatement,
+    },
+    /// A parenthesized sequence expression: `(a, b, c)`.
+    ///
+    /// Each expression is evaluated in turn and the sequence yields the last.
+    Sequence(Vec<ExprData>),
+    /// A call expression: `add(1, 2)`.
+    Call {
+        /// The called expression.
+        function: Box<ExprData>,
+        /// The call arguments.
+        arguments: Vec<ExprData>,
+    },
+}
+
+/// `MatchArm` represents an arm of a match expression.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchArm {
+    /// The pattern compared against the scrutinee.
+    pub pattern: Pattern,
+    /// The expression of the arm.
+    pub body: ExprData,
+}
+
+/// `Pattern` represents the pattern of a match arm.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// A literal value: `1` or `true`.
+    Literal(ExprData),
+    /// The default pattern: `_`.
+    Wildcard,
+}
+
+impl fmt::Display for ExprData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Prefix { operator, right } => write!(f, "({operator}{right})"),
+            Self::Infix {
+                left,
+                operator,
+                right,
+            } => write!(f, "({left} {operator} {right})"),
+            Self::Conditional {
+                condition,
+                consequence,
+                alternative,
+            } => write!(f, "({condition} ? {consequence} : {alternative})"),
+            Self::Match { scrutinee, arms } => {
+                let arms: Vec<String> = arms.iter().map(MatchArm::to_string).collect();
+                write!(f, "match {scrutinee} {{ {} }}", arms.join(", "))
+            }
+            Self::Function { parameters, body } => {
+                write!(f, "fn({}) {body}", parameters.join(", "))
+            }
+            Self::Sequence(exprs) => {
+                let exprs: Vec<String> = exprs.iter().map(ExprData::to_string).collect();
+                write!(f, "({})", exprs.join(", "))
+            }
+            Self::Call {
+                function,
+                arguments,
+            } => {
+                let arguments: Vec<String> = arguments.iter().map(ExprData::to_string).collect();
+                write!(f, "{function}({})", arguments.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} => {}", self.pattern, self.body)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(expr) => write!(f, "{expr}"),
+            Self::Wildcard => write!(f, "_"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_str;
+
+    #[test]
+    fn display_expressions() {
+        let tests = [
+            ("5", "5"),
+            ("-a * b", "((-a) * b)"),
+            ("a + b * c == !d", "((a + (b * c)) == (!d))"),
+            ("(a + b) / c", "((a + b) / c)"),
+            ("fn(x, y) { x + y; }", "fn(x, y) { (x + y) }"),
+        ];
+
+        for (input, expected) in tests {
+            let (program, errors) = parse_str(input);
+            assert!(errors.is_empty(), "{input}: {errors:?}");
+            assert_eq!(program.to_string(), expected);
+        }
+    }
 }
@@ -2,28 +2,82 @@
 //!
 //! This module implement the lexer.
 
+mod buffer;
+mod reader;
+
 use std::fmt;
+use std::io::BufRead;
+use std::iter;
 
-use std::iter::Peekable;
 use std::str::CharIndices;
 
-use super::span::Span;
+use super::error::Error;
+use super::span::{self, Span};
 use super::token::{Token, TokenKind, TokenValue};
 
+pub use self::buffer::TokenBuffer;
+pub use self::reader::{DecodeError, ReaderChars};
+
+/// Input of the lexer: the characters of the source along with their byte
+/// offsets.
+pub trait Input: Iterator<Item = (usize, char)> {
+    /// Returns the next character without consuming it.
+    fn peek(&mut self) -> Option<(usize, char)>;
+
+    /// Takes the error found while decoding the last consumed character, or
+    /// the end of the input.
+    fn take_error(&mut self) -> Option<DecodeError> {
+        None
+    }
+}
+
+impl Input for CharIndices<'_> {
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.clone().next()
+    }
+}
+
 /// Lexer type.
 pub struct Lexer<I>
 where
-    I: Iterator<Item = (usize, char)>,
+    I: Input,
 {
     /// The current input string.
-    pub chars: Peekable<I>,
+    pub chars: I,
     /// The current line number in the input.
     pub lineno: usize,
+    /// The lexer configuration.
+    pub config: LexerConfig,
+    /// The lexical errors.
+    pub errors: Vec<Error>,
+    /// The current column in the line, counted in characters.
+    column: usize,
+    /// The byte offset of the next character in the input.
+    offset: usize,
+    /// The byte offset of the input in the whole source text.
+    start: usize,
+    /// The byte offset where a shebang may start, past any byte order mark,
+    /// or `None` if the input does not start the source text.
+    shebang: Option<usize>,
+    /// Whether a lexing error was found.
+    failed: bool,
+    /// The buffer reused to accumulate the text of words and numbers.
+    scratch: String,
+}
+
+/// Lexer configuration.
+#[derive(Debug, Clone, Default)]
+pub struct LexerConfig {
+    /// Treats `#` as the start of a line comment.
+    pub hash_comments: bool,
+    /// Stops lexing at the first illegal character instead of producing an
+    /// `Unknown` token.
+    pub strict: bool,
 }
 
 impl<I> fmt::Debug for Lexer<I>
 where
-    I: Iterator<Item = (usize, char)>,
+    I: Input,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Lexer")
@@ -34,16 +88,38 @@ where
 
 impl<'a> Lexer<CharIndices<'a>> {
     /// Creates new lexer with given string input.
-    pub fn from_text(input: &'a str) -> Lexer<CharIndices> {
-        let chars = input.char_indices().peekable();
-        Self { chars, lineno: 1 }
+    pub fn from_text(input: &'a str) -> Lexer<CharIndices<'a>> {
+        Lexer::new(input.char_indices(), Span::new(1, 0))
+    }
+
+    /// Creates new lexer over `input`, the part of a source text that starts
+    /// at the position `start`.
+    ///
+    /// The spans are relative to the whole source text, and only the start of
+    /// the source text may hold a byte order mark or a shebang.
+    fn resume(input: &'a str, start: Span) -> Self {
+        Lexer::new(input.char_indices(), start)
+    }
+
+    /// Returns the portion of the input that has not been lexed yet.
+    pub fn remaining(&self) -> &'a str {
+        self.chars.as_str()
+    }
+}
+
+impl<R: BufRead> Lexer<ReaderChars<R>> {
+    /// Creates new lexer reading its input from the reader.
+    ///
+    /// The input is decoded as UTF-8 while it is lexed.
+    pub fn from_reader(reader: R) -> Self {
+        Lexer::new(ReaderChars::new(reader), Span::new(1, 0))
     }
 }
 
 /// Matches an operator.
 macro_rules! operator {
     () => {
-        '+' | '-' | '*' | '/' | '!' | '=' | '<' | '>'
+        '+' | '-' | '*' | '/' | '!' | '=' | '<' | '>' | '.' | '|'
     };
 }
 
@@ -72,133 +148,289 @@ macro_rules! delimiter_kind {
 /// Lookup keyword.
 macro_rules! lookup_keyword {
     ($word:expr) => {
-        match $word.as_str() {
-            "let" => TokenKind::Let,
-            "true" => TokenKind::True,
-            "false" => TokenKind::False,
-            "if" => TokenKind::If,
-            "else" => TokenKind::Else,
-            "return" => TokenKind::Return,
-            "fn" => TokenKind::Function,
-            _ => TokenKind::Ident,
-        }
+        TokenKind::keyword($word.as_str()).unwrap_or(TokenKind::Ident)
     };
 }
 
 impl<I> Lexer<I>
 where
-    I: Iterator<Item = (usize, char)>,
+    I: Input,
 {
-    /// Eats the whitespace from input.
+    /// Creates new lexer over the character indices of the input found at
+    /// the position `start` of the source text.
+    ///
+    /// A UTF-8 byte order mark at the start of the source text is skipped
+    /// and does not count as a column.
+    fn new(mut chars: I, start: Span) -> Self {
+        let mut offset = start.offset;
+        if offset == 0 && chars.peek().is_some_and(|(_, c)| c == '\u{FEFF}') {
+            chars.next();
+            offset = '\u{FEFF}'.len_utf8();
+        }
+        Self {
+            chars,
+            lineno: start.lineno,
+            config: LexerConfig::default(),
+            errors: Vec::new(),
+            column: start.column_pos,
+            offset,
+            start: start.offset,
+            shebang: (start.offset == 0).then_some(offset),
+            failed: false,
+            scratch: String::new(),
+        }
+    }
+
+    /// Sets the lexer configuration.
+    pub const fn with_config(mut self, config: LexerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Eats the whitespace and the comments from input.
     fn eat_whitespace(&mut self) {
-        while self.lookahead(|&x| x.is_whitespace()).is_some() {}
+        loop {
+            while self.lookahead(|&x| x.is_whitespace()).is_some() {}
+            if !self.eat_comment() {
+                break;
+            }
+        }
+    }
+
+    /// Eats a line comment. Returns false if there is no comment.
+    fn eat_comment(&mut self) -> bool {
+        if !self.config.hash_comments || self.lookahead(|&x| x == '#').is_none() {
+            return false;
+        }
+        while self.lookahead(|&x| x != '\n').is_some() {}
+        true
     }
 
     /// Returns the next token.
+    ///
+    /// In strict mode, returns `None` once a lexing error is found.
     pub fn next_token(&mut self) -> Option<Token> {
+        if self.config.strict && self.failed {
+            return None;
+        }
+        let token = self.lex_token()?;
+        debug_assert_eof_value(&token);
+        Some(token)
+    }
+
+    /// Lexes the next token.
+    fn lex_token(&mut self) -> Option<Token> {
         self.eat_whitespace();
 
-        let mut token = Token::new(TokenValue::Eof, TokenKind::Eof, Span::new(self.lineno, 0));
-        let Some((position, literal)) =  self.chars.next() else { return Some(token) };
+        let start = self.position();
+        let Some((position, literal)) = self.chars.next() else {
+            self.check_input(start);
+            if self.config.strict && self.failed {
+                return None;
+            }
+            return Some(Token::new(TokenValue::Eof, TokenKind::Eof, start));
+        };
+        self.advance(position, literal);
+        if self.config.strict && self.failed {
+            return None;
+        }
 
-        token.span = Span::new(self.lineno, position);
+        // A `#!` line at the very start of input is a shebang.
+        if self.shebang == Some(start.offset)
+            && literal == '#'
+            && self.lookahead(|&x| x == '!').is_some()
+        {
+            while self.lookahead(|&x| x != '\n').is_some() {}
+            return self.next_token();
+        }
 
-        match literal {
-            ',' => {
-                token.value = TokenValue::Comma;
-                token.kind = TokenKind::Comma;
-            }
-            ';' => {
-                token.value = TokenValue::Semi;
-                token.kind = TokenKind::Semi;
-            }
+        let (value, kind) = match literal {
+            ',' => (TokenValue::Comma, TokenKind::Comma),
+            ';' => (TokenValue::Semi, TokenKind::Semi),
+            ':' => (TokenValue::Colon, TokenKind::Colon),
+            '?' => (TokenValue::Operator("?"), TokenKind::Question),
             operator!() => {
-                if literal == '=' {
-                    if let Some((_, ch)) = self.lookahead(|&x| x == '=') {
-                        token.value = TokenValue::Operator(format!("{literal}{ch}"));
-                        token.kind = TokenKind::EqEq;
-                    } else {
-                        token.value = TokenValue::Operator(literal.into());
-                        token.kind = TokenKind::Eq;
-                    }
-                } else if literal == '!' {
-                    if let Some((_, ch)) = self.lookahead(|&x| x == '=') {
-                        token.value = TokenValue::Operator(format!("{literal}{ch}"));
-                        token.kind = TokenKind::Ne;
-                    } else {
-                        token.value = TokenValue::Operator(literal.into());
-                        token.kind = TokenKind::Not;
+                let kind = match literal {
+                    '=' if self.lookahead(|&x| x == '=').is_some() => TokenKind::EqEq,
+                    '=' if self.lookahead(|&x| x == '>').is_some() => TokenKind::FatArrow,
+                    '!' if self.lookahead(|&x| x == '=').is_some() => TokenKind::Ne,
+                    '.' if self.lookahead(|&x| x == '.').is_some() => {
+                        if self.lookahead(|&x| x == '=').is_some() {
+                            TokenKind::DotDotEq
+                        } else {
+                            TokenKind::DotDot
+                        }
                     }
-                } else {
-                    let literal: String = literal.into();
-                    let kind = TokenKind::from(literal.as_str());
-                    token.value = TokenValue::Operator(literal);
-                    token.kind = kind;
-                }
-            }
-            delimiter!() => {
-                token.value = TokenValue::Delimiter(literal);
-                token.kind = delimiter_kind!(literal);
+                    '|' if self.lookahead(|&x| x == '|').is_some() => TokenKind::OrOr,
+                    '|' if self.lookahead(|&x| x == '>').is_some() => TokenKind::Pipe,
+                    _ => TokenKind::from(literal.encode_utf8(&mut [0; 4]) as &str),
+                };
+                (TokenValue::Operator(kind.as_str()), kind)
             }
+            delimiter!() => (TokenValue::Delimiter(literal), delimiter_kind!(literal)),
             _ => {
                 if is_identifier(&literal) {
-                    let mut ident = String::from(literal);
-                    if let Some(value) = self.lex_identifier() {
-                        ident.push_str(&value);
-                    }
+                    self.scratch.clear();
+                    self.scratch.push(literal);
+                    self.lex_identifier();
 
-                    let kind = lookup_keyword!(ident);
-                    token.value = TokenValue::Word(ident);
-                    token.kind = kind;
+                    let kind = lookup_keyword!(self.scratch);
+                    (TokenValue::Word(self.scratch.as_str().into()), kind)
                 } else if literal.is_ascii_digit() {
-                    let mut digits = String::from(literal);
-                    if let Some(extra_digits) = self.lex_int() {
-                        digits.push_str(&extra_digits);
+                    self.scratch.clear();
+                    self.scratch.push(literal);
+                    self.lex_int();
+                    if self.chars.peek().is_some_and(|(_, c)| is_identifier(&c)) {
+                        self.lex_identifier();
+                        let digits = String::from(self.scratch.as_str());
+                        self.report(Error::InvalidNumericLiteral {
+                            span: start.with_len(digits.len()),
+                            literal: digits.clone(),
+                        });
+                        if self.config.strict {
+                            return None;
+                        }
+                        (TokenValue::Number(digits), TokenKind::Unknown)
+                    } else {
+                        (
+                            TokenValue::Number(self.scratch.as_str().into()),
+                            TokenKind::Number,
+                        )
                     }
-                    token = Token::new(
-                        TokenValue::Number(digits),
-                        TokenKind::Number,
-                        Span::new(self.lineno, position),
-                    );
+                } else if self.config.strict {
+                    self.report(Error::IllegalCharacter {
+                        ch: literal,
+                        span: start.with_len(literal.len_utf8()),
+                    });
+                    return None;
                 } else {
-                    token.value = TokenValue::Unknown(literal);
-                    token.kind = TokenKind::Unknown;
+                    (TokenValue::Unknown(literal), TokenKind::Unknown)
                 }
             }
         };
-        Some(token)
+
+        let len = self.offset - start.offset;
+        Some(Token::new(value, kind, start.with_len(len)))
     }
 
-    /// Returns the identitifer.
-    fn lex_identifier(&mut self) -> Option<String> {
-        let mut ident = String::new();
-        while let Some((_, ch)) = self.lookahead(is_identifier) {
-            ident.push(ch);
+    /// Returns the next token, or `None` at the end of input.
+    ///
+    /// Unlike [`Lexer::next_token`], a lexing error is returned as `Err`
+    /// instead of being recorded in `errors`. In strict mode, the lexer still
+    /// stops after the first error.
+    pub fn try_next_token(&mut self) -> Result<Option<Token>, Error> {
+        let count = self.errors.len();
+        let token = self.next_token();
+        if self.errors.len() > count {
+            if let Some(err) = self.errors.pop() {
+                return Err(err);
+            }
         }
-        if ident.is_empty() {
-            return None;
+        Ok(token.filter(|tok| tok.kind != TokenKind::Eof))
+    }
+
+    /// Advances the lexer past the next semicolon or up to the end of input.
+    ///
+    /// Returns the number of tokens skipped before the semicolon.
+    pub fn skip_to_semicolon(&mut self) -> usize {
+        let mut skipped = 0;
+        while let Some(tok) = self.next_token() {
+            if matches!(tok.kind, TokenKind::Semi | TokenKind::Eof) {
+                break;
+            }
+            skipped += 1;
+        }
+        skipped
+    }
+
+    /// Returns an iterator over the remaining tokens, excluding `Eof`.
+    pub fn tokens(mut self) -> impl Iterator<Item = Token> {
+        iter::from_fn(move || self.next_token().filter(|tok| tok.kind != TokenKind::Eof))
+    }
+
+    /// Returns an iterator over the spans of the remaining tokens.
+    pub fn spans(self) -> impl Iterator<Item = Span> {
+        self.tokens().map(|tok| tok.span)
+    }
+
+    /// Returns an iterator over the remaining tokens paired with their text in `source`.
+    ///
+    /// `source` must be the input the lexer was created from.
+    pub fn with_source_slices(self, source: &str) -> impl Iterator<Item = (Token, &str)> {
+        self.tokens().map(move |tok| {
+            let text = span::snippet(source, &tok.span).unwrap_or_default();
+            (tok, text)
+        })
+    }
+
+    /// Appends the rest of the identifier to the scratch buffer.
+    fn lex_identifier(&mut self) {
+        while let Some((_, ch)) = self.lookahead(is_identifier) {
+            self.scratch.push(ch);
         }
-        Some(ident)
     }
 
     /// Inspect next element.
     fn lookahead(&mut self, func: impl FnOnce(&char) -> bool) -> Option<(usize, char)> {
-        self.chars.next_if(|(_, c)| func(c))
+        let (position, ch) = self.chars.peek().filter(|(_, c)| func(c))?;
+        self.chars.next();
+        self.advance(position, ch);
+        Some((position, ch))
     }
 
-    /// Return a digit.
-    fn lex_int(&mut self) -> Option<String> {
-        let mut digits = String::new();
-        while let Some((_, ch)) = self.lookahead(|&x| x.is_ascii_digit()) {
-            digits.push(ch);
+    /// Moves the current position past the character found at `position`.
+    ///
+    /// Records the error found while decoding the character, if any.
+    fn advance(&mut self, position: usize, ch: char) {
+        self.check_input(self.position().with_offset(self.start + position));
+        self.offset = self.start + position + ch.len_utf8();
+        if ch == '\n' {
+            self.lineno += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
         }
-        if digits.is_empty() {
-            return None;
+    }
+
+    /// Records the error found by the input at `span`, if any.
+    fn check_input(&mut self, span: Span) {
+        let error = match self.chars.take_error() {
+            Some(DecodeError::InvalidUtf8 { len }) => Error::InvalidUtf8 {
+                span: span.with_len(len),
+            },
+            Some(DecodeError::Io(error)) => Error::Io { error, span },
+            None => return,
+        };
+        self.report(error);
+    }
+
+    /// Records a lexing error.
+    fn report(&mut self, error: Error) {
+        self.errors.push(error);
+        self.failed = true;
+    }
+
+    /// Returns the position of the next character as an empty span.
+    const fn position(&self) -> Span {
+        Span::new(self.lineno, self.column).with_offset(self.offset)
+    }
+
+    /// Appends the rest of the digits to the scratch buffer.
+    fn lex_int(&mut self) {
+        while let Some((_, ch)) = self.lookahead(|&x| x.is_ascii_digit()) {
+            self.scratch.push(ch);
         }
-        Some(digits)
     }
 }
 
+/// Asserts in debug builds that a token of the `Eof` kind has the `Eof` value.
+fn debug_assert_eof_value(token: &Token) {
+    debug_assert!(
+        token.kind != TokenKind::Eof || token.value == TokenValue::Eof,
+        "Eof token with non-Eof value: {token:?}"
+    );
+}
+
 /// Returns true if the character is a letter or underscore.
 fn is_identifier(c: &char) -> bool {
     c.is_alphabetic() || *c == '_'
@@ -206,9 +438,57 @@ fn is_identifier(c: &char) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::Lexer;
+    use std::io;
+
+    use super::{debug_assert_eof_value, Input, Lexer, LexerConfig};
+    use crate::error::Error;
+    use crate::span::Span;
     use crate::token::TokenKind;
-    use crate::token::TokenValue;
+    use crate::token::{Token, TokenValue};
+
+    /// Returns the index and the tokens at the first position where the two
+    /// token streams differ, or `None` when they are equal.
+    fn first_token_diff<'a>(
+        expected: &'a [Token],
+        actual: &'a [Token],
+    ) -> Option<(usize, Option<&'a Token>, Option<&'a Token>)> {
+        (0..expected.len().max(actual.len())).find_map(|index| {
+            let (left, right) = (expected.get(index), actual.get(index));
+            (left != right).then_some((index, left, right))
+        })
+    }
+
+    /// Lexes the input with the configuration until the end of input,
+    /// including the `Eof` token.
+    fn lex_all_with(input: &str, config: LexerConfig) -> Vec<Token> {
+        lex_tokens(Lexer::from_text(input).with_config(config))
+    }
+
+    /// Runs the lexer until the end of input, including the `Eof` token.
+    fn lex_tokens<I: Input>(mut lexer: Lexer<I>) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next_token() {
+            let eof = token.kind == TokenKind::Eof;
+            tokens.push(token);
+            if eof {
+                break;
+            }
+        }
+        tokens
+    }
+
+    /// Lexes the input until the end of input, including the `Eof` token.
+    fn lex_all(input: &str) -> Vec<Token> {
+        lex_all_with(input, LexerConfig::default())
+    }
+
+    /// Returns the value and the kind of each token.
+    fn values(tokens: &[Token]) -> Vec<(TokenValue, TokenKind)> {
+        tokens
+            .iter()
+            .map(|token| (token.value.clone(), token.kind))
+            .collect()
+    }
 
     #[test]
     fn create_lexemes_successfully() {
@@ -254,17 +534,17 @@ let snow = 9;"#;
         let tests = [
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("five".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Number("5".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("ten".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Number("10".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("add".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Word("fn".into()), Function),
             (TokenValue::Delimiter('('), Lparen),
             (TokenValue::Word("x".into()), Ident),
@@ -273,14 +553,14 @@ let snow = 9;"#;
             (TokenValue::Delimiter(')'), Rparen),
             (TokenValue::Delimiter('{'), Lbrace),
             (TokenValue::Word("x".into()), Ident),
-            (TokenValue::Operator("+".into()), Plus),
+            (TokenValue::Operator("+"), Plus),
             (TokenValue::Word("y".into()), Ident),
             (TokenValue::Semi, Semi),
             (TokenValue::Delimiter('}'), Rbrace),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("result".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Word("add".into()), Ident),
             (TokenValue::Delimiter('('), Lparen),
             (TokenValue::Word("five".into()), Ident),
@@ -288,22 +568,22 @@ let snow = 9;"#;
             (TokenValue::Word("ten".into()), Ident),
             (TokenValue::Delimiter(')'), Rparen),
             (TokenValue::Semi, Semi),
-            (TokenValue::Operator("!".into()), Not),
-            (TokenValue::Operator("-".into()), Minus),
-            (TokenValue::Operator("/".into()), Slash),
-            (TokenValue::Operator("*".into()), Star),
+            (TokenValue::Operator("!"), Not),
+            (TokenValue::Operator("-"), Minus),
+            (TokenValue::Operator("/"), Slash),
+            (TokenValue::Operator("*"), Star),
             (TokenValue::Number("5".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Number("5".into()), Number),
-            (TokenValue::Operator("<".into()), Lt),
+            (TokenValue::Operator("<"), Lt),
             (TokenValue::Number("10".into()), Number),
-            (TokenValue::Operator(">".into()), Gt),
+            (TokenValue::Operator(">"), Gt),
             (TokenValue::Number("4".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("if".into()), If),
             (TokenValue::Delimiter('('), Lparen),
             (TokenValue::Number("5".into()), Number),
-            (TokenValue::Operator("<".into()), Lt),
+            (TokenValue::Operator("<"), Lt),
             (TokenValue::Number("10".into()), Number),
             (TokenValue::Delimiter(')'), Rparen),
             (TokenValue::Delimiter('{'), Lbrace),
@@ -318,21 +598,21 @@ let snow = 9;"#;
             (TokenValue::Semi, Semi),
             (TokenValue::Delimiter('}'), Rbrace),
             (TokenValue::Number("10".into()), Number),
-            (TokenValue::Operator("==".into()), EqEq),
+            (TokenValue::Operator("=="), EqEq),
             (TokenValue::Number("10".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Number("10".into()), Number),
-            (TokenValue::Operator("!=".into()), Ne),
+            (TokenValue::Operator("!="), Ne),
             (TokenValue::Number("9".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("nine".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Number("9".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Word("let".into()), Let),
             (TokenValue::Word("snow".into()), Ident),
-            (TokenValue::Operator("=".into()), Eq),
+            (TokenValue::Operator("="), Eq),
             (TokenValue::Number("9".into()), Number),
             (TokenValue::Semi, Semi),
             (TokenValue::Eof, Eof),
@@ -353,4 +633,501 @@ let snow = 9;"#;
             );
         }
     }
+
+    #[test]
+    fn track_lines_and_columns() {
+        let mut lexer = Lexer::from_text("let a = 1;\nlet é = 2;\n  @");
+        let mut spans = Vec::new();
+        while let Some(token) = lexer.next_token() {
+            spans.push(token.span.to_string());
+            if token.kind == TokenKind::Eof {
+                break;
+            }
+        }
+        assert_eq!(
+            spans,
+            ["1:0", "1:4", "1:6", "1:8", "1:9", "2:0", "2:4", "2:6", "2:8", "2:9", "3:2", "3:3"]
+        );
+    }
+
+    #[test]
+    fn eof_token_has_eof_value() {
+        let expected = Token::new(TokenValue::Eof, TokenKind::Eof, Span::new(1, 0));
+
+        let mut lexer = Lexer::from_text("x  ");
+        lexer.next_token().expect("failed to create lexeme");
+        let token = lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(expected, token);
+        assert_eq!(TokenValue::Eof, token.value);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Eof token with non-Eof value")]
+    fn assert_eof_token_value() {
+        let token = Token::new(TokenValue::Semi, TokenKind::Eof, Span::new(1, 0));
+        debug_assert_eof_value(&token);
+    }
+
+    #[test]
+    fn skip_to_semicolon_stops_after_semi() {
+        let mut lexer = Lexer::from_text("garbage tokens ; rest");
+        assert_eq!(lexer.skip_to_semicolon(), 2);
+
+        let token = lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(TokenValue::Word("rest".into()), token.value);
+
+        assert_eq!(lexer.skip_to_semicolon(), 0);
+        let token = lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(TokenKind::Eof, token.kind);
+    }
+
+    #[test]
+    fn lex_hash_comments() {
+        let config = LexerConfig {
+            hash_comments: true,
+            ..LexerConfig::default()
+        };
+        let tokens = lex_all_with("# comment\nlet x # trailing\n;", config);
+        assert_eq!(
+            values(&tokens),
+            [
+                (TokenValue::Word("let".into()), TokenKind::Let),
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Semi, TokenKind::Semi),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+
+        assert_eq!(
+            values(&lex_all("# comment")),
+            [
+                (TokenValue::Unknown('#'), TokenKind::Unknown),
+                (TokenValue::Word("comment".into()), TokenKind::Ident),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_multi_char_words_and_numbers() {
+        assert_eq!(
+            values(&lex_all("état_1 12345+x")),
+            [
+                (TokenValue::Word("état_".into()), TokenKind::Ident),
+                (TokenValue::Number("1".into()), TokenKind::Number),
+                (TokenValue::Number("12345".into()), TokenKind::Number),
+                (TokenValue::Operator("+"), TokenKind::Plus),
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_golden_token_stream() {
+        let source = "let add = fn(x, y) { x + y; };
+!-/*5 < 10 >= 4 != 9 == 10;
+if (a) { return true; } else { return false; }
+[é, _b]
+12ab @";
+        let expected = r#"Let Word("let") @ 1:0
+Ident Word("add") @ 1:4
+Eq Operator("=") @ 1:8
+Function Word("fn") @ 1:10
+Lparen Delimiter('(') @ 1:12
+Ident Word("x") @ 1:13
+Comma Comma @ 1:14
+Ident Word("y") @ 1:16
+Rparen Delimiter(')') @ 1:17
+Lbrace Delimiter('{') @ 1:19
+Ident Word("x") @ 1:21
+Plus Operator("+") @ 1:23
+Ident Word("y") @ 1:25
+Semi Semi @ 1:26
+Rbrace Delimiter('}') @ 1:28
+Semi Semi @ 1:29
+Not Operator("!") @ 2:0
+Minus Operator("-") @ 2:1
+Slash Operator("/") @ 2:2
+Star Operator("*") @ 2:3
+Number Number("5") @ 2:4
+Lt Operator("<") @ 2:6
+Number Number("10") @ 2:8
+Gt Operator(">") @ 2:11
+Eq Operator("=") @ 2:12
+Number Number("4") @ 2:14
+Ne Operator("!=") @ 2:16
+Number Number("9") @ 2:19
+EqEq Operator("==") @ 2:21
+Number Number("10") @ 2:24
+Semi Semi @ 2:26
+If Word("if") @ 3:0
+Lparen Delimiter('(') @ 3:3
+Ident Word("a") @ 3:4
+Rparen Delimiter(')') @ 3:5
+Lbrace Delimiter('{') @ 3:7
+Return Word("return") @ 3:9
+True Word("true") @ 3:16
+Semi Semi @ 3:20
+Rbrace Delimiter('}') @ 3:22
+Else Word("else") @ 3:24
+Lbrace Delimiter('{') @ 3:29
+Return Word("return") @ 3:31
+False Word("false") @ 3:38
+Semi Semi @ 3:43
+Rbrace Delimiter('}') @ 3:45
+Lbracket Delimiter('[') @ 4:0
+Ident Word("é") @ 4:1
+Comma Comma @ 4:2
+Ident Word("_b") @ 4:4
+Rbracket Delimiter(']') @ 4:6
+Unknown Number("12ab") @ 5:0
+Unknown Unknown('@') @ 5:5
+Eof Eof @ 5:6"#;
+
+        let actual: Vec<String> = lex_all(source)
+            .iter()
+            .map(|token| format!("{:?} {:?} @ {}", token.kind, token.value, token.span))
+            .collect();
+        assert_eq!(actual.join("\n"), expected);
+    }
+
+    #[test]
+    fn first_token_diff_reports_mismatch() {
+        let expected = lex_all("let x = 5;");
+        assert_eq!(first_token_diff(&expected, &lex_all("let x = 5;")), None);
+
+        let actual = lex_all("let y = 5;");
+        let (index, left, right) =
+            first_token_diff(&expected, &actual).expect("streams should differ");
+        assert_eq!(index, 1);
+        assert_eq!(left.map(Token::as_str).as_deref(), Some("x"));
+        assert_eq!(right.map(Token::as_str).as_deref(), Some("y"));
+
+        let actual = lex_all("let x");
+        let (index, left, right) =
+            first_token_diff(&expected, &actual).expect("streams should differ");
+        assert_eq!(index, 2);
+        assert_eq!(left.map(|tok| tok.kind), Some(TokenKind::Eq));
+        assert_eq!(right.map(|tok| tok.kind), Some(TokenKind::Eof));
+    }
+
+    #[test]
+    fn lex_conditional_operator() {
+        assert_eq!(
+            values(&lex_all("x ? 1 : 2")),
+            [
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Operator("?"), TokenKind::Question),
+                (TokenValue::Number("1".into()), TokenKind::Number),
+                (TokenValue::Colon, TokenKind::Colon),
+                (TokenValue::Number("2".into()), TokenKind::Number),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+    }
+
+    #[test]
+    fn operator_value_agrees_with_kind() {
+        let tokens = lex_all("+ - * / ! = == != < > ? => . .. ..= | || |>");
+        let (eof, operators) = tokens.split_last().expect("no tokens");
+        assert_eq!(eof.kind, TokenKind::Eof);
+        assert_eq!(operators.len(), 18);
+        for token in operators {
+            assert_eq!(token.value.operator_kind(), Some(token.kind), "{token:?}");
+        }
+    }
+
+    #[test]
+    fn lex_not_keyword() {
+        let tokens = lex_all("not true");
+        assert_eq!(
+            values(&tokens),
+            [
+                (TokenValue::Word("not".into()), TokenKind::Not),
+                (TokenValue::Word("true".into()), TokenKind::True),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+        assert!(tokens[0].is_keyword());
+    }
+
+    #[test]
+    fn lex_from_reader() {
+        let input = "let café = fn(x, y) { x + y; };\nlet result = café(5, 10);";
+        let expected = lex_all(input);
+        let actual = lex_tokens(Lexer::from_reader(input.as_bytes()));
+        assert_eq!(first_token_diff(&expected, &actual), None);
+        assert_eq!(expected[1].span.column_pos, actual[1].span.column_pos);
+        assert_eq!(expected[20].span.column_pos, actual[20].span.column_pos);
+    }
+
+    #[test]
+    fn lex_invalid_utf8_from_reader() {
+        let input: &[u8] = b"x \xff y \xef\xbf\xbd \xe2\x82";
+        let mut lexer = Lexer::from_reader(input);
+        let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token())
+            .take_while(|token| token.kind != TokenKind::Eof)
+            .collect();
+        assert_eq!(
+            values(&tokens),
+            [
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Unknown('\u{fffd}'), TokenKind::Unknown),
+                (TokenValue::Word("y".into()), TokenKind::Ident),
+                (TokenValue::Unknown('\u{fffd}'), TokenKind::Unknown),
+                (TokenValue::Unknown('\u{fffd}'), TokenKind::Unknown),
+            ]
+        );
+        let spans: Vec<(usize, usize)> = lexer
+            .errors
+            .iter()
+            .map(|err| match err {
+                Error::InvalidUtf8 { span } => (span.offset, span.len),
+                _ => panic!("expected invalid UTF-8 error found {err:?}"),
+            })
+            .collect();
+        assert_eq!(spans, [(2, 1), (10, 2)]);
+        assert_eq!(lexer.errors[0].to_string(), "invalid UTF-8 at 1:2");
+
+        let config = LexerConfig {
+            strict: true,
+            ..LexerConfig::default()
+        };
+        let mut lexer = Lexer::from_reader(input).with_config(config);
+        lexer.next_token().expect("failed to create lexeme");
+        assert!(lexer.next_token().is_none());
+        assert!(matches!(lexer.errors[..], [Error::InvalidUtf8 { .. }]));
+    }
+
+    #[test]
+    fn lex_read_error_from_reader() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+
+        let reader = io::BufReader::new(io::Read::chain(&b"x "[..], FailingReader));
+        let tokens = lex_tokens(Lexer::from_reader(reader));
+        assert_eq!(
+            values(&tokens),
+            [
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+
+        let mut lexer = Lexer::from_reader(io::BufReader::new(FailingReader));
+        lexer.next_token().expect("failed to create lexeme");
+        assert!(matches!(
+            &lexer.errors[..],
+            [Error::Io { span, .. }] if span.offset == 0
+        ));
+    }
+
+    #[test]
+    fn lex_match_arms() {
+        assert_eq!(
+            values(&lex_all("match x { 1 => y, _ => z }")),
+            [
+                (TokenValue::Word("match".into()), TokenKind::Match),
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Delimiter('{'), TokenKind::Lbrace),
+                (TokenValue::Number("1".into()), TokenKind::Number),
+                (TokenValue::Operator("=>"), TokenKind::FatArrow),
+                (TokenValue::Word("y".into()), TokenKind::Ident),
+                (TokenValue::Comma, TokenKind::Comma),
+                (TokenValue::Word("_".into()), TokenKind::Ident),
+                (TokenValue::Operator("=>"), TokenKind::FatArrow),
+                (TokenValue::Word("z".into()), TokenKind::Ident),
+                (TokenValue::Delimiter('}'), TokenKind::Rbrace),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_range_operators() {
+        let tests = [
+            ("1..=5", "..=", TokenKind::DotDotEq),
+            ("1..5", "..", TokenKind::DotDot),
+            ("1.5", ".", TokenKind::Dot),
+        ];
+        for (input, operator, kind) in tests {
+            assert_eq!(
+                values(&lex_all(input)),
+                [
+                    (TokenValue::Number("1".into()), TokenKind::Number),
+                    (TokenValue::Operator(operator), kind),
+                    (TokenValue::Number("5".into()), TokenKind::Number),
+                    (TokenValue::Eof, TokenKind::Eof),
+                ],
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn lex_number_followed_by_identifier() {
+        let mut lexer = Lexer::from_text("123abc");
+        let token = lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(token.value, TokenValue::Number("123abc".into()));
+        assert_eq!(token.kind, TokenKind::Unknown);
+        assert!(matches!(
+            &lexer.errors[..],
+            [Error::InvalidNumericLiteral { literal, span }]
+                if literal == "123abc" && span.column_pos == 0 && span.len == 6
+        ));
+
+        let mut lexer = Lexer::from_text("123 abc");
+        for (value, kind) in [
+            (TokenValue::Number("123".into()), TokenKind::Number),
+            (TokenValue::Word("abc".into()), TokenKind::Ident),
+            (TokenValue::Eof, TokenKind::Eof),
+        ] {
+            let token = lexer.next_token().expect("failed to create lexeme");
+            assert_eq!(value, token.value);
+            assert_eq!(kind, token.kind);
+        }
+        assert!(lexer.errors.is_empty());
+    }
+
+    #[test]
+    fn skip_leading_shebang() {
+        let tokens = lex_all("#!/usr/bin/env monkey\nlet x = 5;");
+        let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Let,
+                TokenKind::Ident,
+                TokenKind::Eq,
+                TokenKind::Number,
+                TokenKind::Semi,
+                TokenKind::Eof
+            ]
+        );
+
+        let tokens = lex_all("x #!y");
+        assert_eq!(tokens[1].value, TokenValue::Unknown('#'));
+        assert_eq!(tokens[2].value, TokenValue::Operator("!"));
+    }
+
+    #[test]
+    fn skip_leading_bom() {
+        let tokens = lex_all("\u{FEFF}let x");
+        assert_eq!(
+            values(&tokens),
+            [
+                (TokenValue::Word("let".into()), TokenKind::Let),
+                (TokenValue::Word("x".into()), TokenKind::Ident),
+                (TokenValue::Eof, TokenKind::Eof),
+            ]
+        );
+        assert_eq!(tokens[0].span, Span::new(1, 0).with_offset(3).with_len(3));
+
+        let tokens = lex_tokens(Lexer::from_reader("\u{FEFF}let".as_bytes()));
+        assert_eq!(tokens[0].kind, TokenKind::Let);
+
+        let tokens = lex_all("\u{FEFF}#!/usr/bin/env monkey\nlet");
+        assert_eq!(tokens[0].kind, TokenKind::Let);
+        assert_eq!(tokens[0].span.lineno, 2);
+
+        let tokens = lex_all("x \u{FEFF}");
+        assert_eq!(tokens[1].value, TokenValue::Unknown('\u{FEFF}'));
+    }
+
+    #[test]
+    fn iterate_spans_and_source_slices() {
+        let source = "let ab = 10;";
+        let spans: Vec<(usize, usize)> = Lexer::from_text(source)
+            .spans()
+            .map(|span| (span.column_pos, span.len))
+            .collect();
+        assert_eq!(spans, [(0, 3), (4, 2), (7, 1), (9, 2), (11, 1)]);
+
+        let slices: Vec<(TokenKind, &str)> = Lexer::from_text(source)
+            .with_source_slices(source)
+            .map(|(tok, text)| (tok.kind, text))
+            .collect();
+        assert_eq!(
+            slices,
+            [
+                (TokenKind::Let, "let"),
+                (TokenKind::Ident, "ab"),
+                (TokenKind::Eq, "="),
+                (TokenKind::Number, "10"),
+                (TokenKind::Semi, ";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_pipe_operators() {
+        let kinds: Vec<TokenKind> = lex_all("x |> f | g || h")
+            .iter()
+            .map(|tok| tok.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Ident,
+                TokenKind::Pipe,
+                TokenKind::Ident,
+                TokenKind::Bar,
+                TokenKind::Ident,
+                TokenKind::OrOr,
+                TokenKind::Ident,
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn try_next_token_returns_errors() {
+        let mut lexer = Lexer::from_text("x 123abc");
+        let token = lexer.try_next_token().expect("unexpected lexing error");
+        assert_eq!(token.map(|tok| tok.kind), Some(TokenKind::Ident));
+        assert!(matches!(
+            lexer.try_next_token(),
+            Err(Error::InvalidNumericLiteral { literal, .. }) if literal == "123abc"
+        ));
+        assert!(lexer.errors.is_empty());
+        assert!(matches!(lexer.try_next_token(), Ok(None)));
+
+        let mut lexer = Lexer::from_text("@").with_config(LexerConfig {
+            strict: true,
+            ..Default::default()
+        });
+        assert!(matches!(
+            lexer.try_next_token(),
+            Err(Error::IllegalCharacter { ch: '@', .. })
+        ));
+
+        let mut lexer = Lexer::from_text("@ x").with_config(LexerConfig {
+            strict: true,
+            ..Default::default()
+        });
+        assert!(lexer.try_next_token().is_err());
+        assert!(matches!(lexer.try_next_token(), Ok(None)));
+        assert!(lexer.next_token().is_none());
+    }
+
+    #[test]
+    fn remaining_source() {
+        let mut lexer = Lexer::from_text("let x = 5;");
+        lexer.next_token().expect("failed to create lexeme");
+        lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(lexer.remaining(), " = 5;");
+
+        while lexer
+            .next_token()
+            .is_some_and(|tok| tok.kind != TokenKind::Eof)
+        {}
+        assert_eq!(lexer.remaining(), "");
+    }
 }
@@ -0,0 +1,253 @@
+//! Token buffer.
+//!
+//! This module implements a token list that can be edited in place.
+
+use std::iter;
+use std::ops::Range;
+
+use super::{Lexer, LexerConfig};
+use crate::span::Span;
+use crate::token::{Token, TokenKind};
+
+/// `TokenBuffer` holds a source text along with its tokens.
+///
+/// Editing the source only re-lexes the tokens around the edited range and
+/// shifts the spans of the tokens after it.
+#[derive(Debug)]
+pub struct TokenBuffer {
+    source: String,
+    tokens: Vec<Token>,
+    config: LexerConfig,
+}
+
+impl TokenBuffer {
+    /// Creates new buffer by lexing the whole source.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self::with_config(source, LexerConfig::default())
+    }
+
+    /// Creates new buffer by lexing the whole source with the configuration.
+    pub fn with_config(source: impl Into<String>, config: LexerConfig) -> Self {
+        let source = source.into();
+        let tokens = Lexer::from_text(&source)
+            .with_config(config.clone())
+            .tokens()
+            .collect();
+        Self {
+            source,
+            tokens,
+            config,
+        }
+    }
+
+    /// Returns the source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the tokens, excluding `Eof`.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Replaces the byte `range` of the source with `text`.
+    ///
+    /// The tokens touching the range, plus one on each side, are re-lexed.
+    /// Returns the indices of the re-lexed tokens in the updated buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or not on a char boundary.
+    pub fn replace(&mut self, range: Range<usize>, text: &str) -> Range<usize> {
+        let first = self
+            .tokens
+            .iter()
+            .position(|tok| tok.span.offset + tok.span.len >= range.start)
+            .map_or(self.tokens.len(), |index| index.saturating_sub(1));
+        let last = self
+            .tokens
+            .iter()
+            .rposition(|tok| tok.span.offset <= range.end)
+            .map_or(0, |index| (index + 2).min(self.tokens.len()));
+        let mut last = last.max(first);
+
+        // The first token is re-lexed from the start of the source, which may
+        // hold a byte order mark or a shebang.
+        let start = if first == 0 {
+            0
+        } else {
+            self.tokens
+                .get(first)
+                .map_or(range.start, |tok| tok.span.offset.min(range.start))
+        };
+        let mut end = self.tokens[first..last].last().map_or(range.end, |tok| {
+            (tok.span.offset + tok.span.len).max(range.end)
+        });
+
+        // A comment started by the edit runs to the end of the line.
+        if self.config.hash_comments {
+            end = self.source[end..]
+                .find('\n')
+                .map_or(self.source.len(), |i| end + i);
+            last += self.tokens[last..]
+                .iter()
+                .take_while(|tok| tok.span.offset < end)
+                .count();
+        }
+
+        let start = self.position_at(start);
+        let old_end = self.position_at(end);
+
+        self.source.replace_range(range.clone(), text);
+        let new_end = end + text.len() - range.len();
+
+        let mut lexer = Lexer::resume(&self.source[start.offset..new_end], start)
+            .with_config(self.config.clone());
+        let relexed: Vec<Token> =
+            iter::from_fn(|| lexer.next_token().filter(|tok| tok.kind != TokenKind::Eof)).collect();
+        let new_end = lexer.position();
+        let count = relexed.len();
+        self.tokens.splice(first..last, relexed);
+
+        for tok in &mut self.tokens[first + count..] {
+            let span = &mut tok.span;
+            if span.lineno == old_end.lineno {
+                span.column_pos = span.column_pos + new_end.column_pos - old_end.column_pos;
+            }
+            span.lineno = span.lineno + new_end.lineno - old_end.lineno;
+            span.offset = span.offset + new_end.offset - old_end.offset;
+        }
+
+        first..first + count
+    }
+
+    /// Returns the position of the byte `offset`, which must not be inside a
+    /// token, as an empty span.
+    fn position_at(&self, offset: usize) -> Span {
+        let index = self.tokens.partition_point(|tok| tok.span.offset <= offset);
+        let mut span = match index.checked_sub(1) {
+            Some(index) => self.tokens[index].span,
+            None if self.source.starts_with('\u{FEFF}') && offset > 0 => {
+                Span::new(1, 0).with_offset('\u{FEFF}'.len_utf8())
+            }
+            None => Span::new(1, 0),
+        };
+        for ch in self.source[span.offset..offset].chars() {
+            if ch == '\n' {
+                span.lineno += 1;
+                span.column_pos = 0;
+            } else {
+                span.column_pos += 1;
+            }
+        }
+        span.with_offset(offset).with_len(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span;
+    use crate::token::TokenValue;
+
+    fn spans(buffer: &TokenBuffer) -> Vec<usize> {
+        buffer.tokens().iter().map(|tok| tok.span.offset).collect()
+    }
+
+    fn positions(buffer: &TokenBuffer) -> Vec<Span> {
+        buffer.tokens().iter().map(|tok| tok.span).collect()
+    }
+
+    #[test]
+    fn replace_relexes_local_tokens() {
+        let mut buffer = TokenBuffer::new("let x = 5; let y = 10;");
+        let before = spans(&buffer);
+
+        let relexed = buffer.replace(4..5, "abc");
+        assert_eq!(buffer.source(), "let abc = 5; let y = 10;");
+        assert_eq!(relexed, 0..3);
+        assert_eq!(buffer.tokens()[1].value, TokenValue::Word("abc".into()));
+
+        let after = spans(&buffer);
+        assert_eq!(after[..2], before[..2]);
+        for (old, new) in before[3..].iter().zip(&after[3..]) {
+            assert_eq!(*new, old + 2);
+        }
+
+        for tok in buffer.tokens() {
+            assert_eq!(
+                span::snippet(buffer.source(), &tok.span),
+                Some(tok.as_str().as_ref())
+            );
+        }
+    }
+
+    #[test]
+    fn replace_matches_full_relex() {
+        let mut buffer = TokenBuffer::new("let x = 5 + 6;");
+        buffer.replace(9..12, "");
+        buffer.replace(8..8, "12");
+
+        let expected = TokenBuffer::new("let x = 1256;");
+        assert_eq!(buffer.source(), expected.source());
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+    }
+
+    #[test]
+    fn replace_updates_lines_and_columns() {
+        let mut buffer = TokenBuffer::new("let a = 1; let b = a;\nlet c = b;");
+        buffer.replace(10..11, "\n  ");
+        buffer.replace(0..3, "let\u{e9}");
+
+        let expected = TokenBuffer::new("let\u{e9} a = 1;\n  let b = a;\nlet c = b;");
+        assert_eq!(buffer.source(), expected.source());
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+
+        buffer.replace(12..15, "");
+        let expected = TokenBuffer::new("let\u{e9} a = 1;let b = a;\nlet c = b;");
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+    }
+
+    #[test]
+    fn replace_only_skips_shebang_at_start() {
+        let mut buffer = TokenBuffer::new("a #!b");
+        buffer.replace(4..5, "c");
+
+        let expected = TokenBuffer::new("a #!c");
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+
+        let mut buffer = TokenBuffer::new("a \u{FEFF}b");
+        buffer.replace(5..6, "c");
+        assert_eq!(buffer.tokens(), TokenBuffer::new("a \u{FEFF}c").tokens());
+
+        let mut buffer = TokenBuffer::new("\u{FEFF}x y");
+        buffer.replace(3..3, "#!");
+        let expected = TokenBuffer::new("\u{FEFF}#!x y");
+        assert!(expected.tokens().is_empty());
+        assert_eq!(buffer.tokens(), expected.tokens());
+
+        let mut buffer = TokenBuffer::new("\u{FEFF}x\ny");
+        buffer.replace(3..4, "zz");
+        let expected = TokenBuffer::new("\u{FEFF}zz\ny");
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+    }
+
+    #[test]
+    fn replace_keeps_lexer_config() {
+        let config = LexerConfig {
+            hash_comments: true,
+            ..LexerConfig::default()
+        };
+        let mut buffer = TokenBuffer::with_config("x = 1; y z\nw", config.clone());
+        buffer.replace(7..7, "#");
+
+        let expected = TokenBuffer::with_config("x = 1; #y z\nw", config);
+        assert_eq!(buffer.tokens(), expected.tokens());
+        assert_eq!(positions(&buffer), positions(&expected));
+    }
+}
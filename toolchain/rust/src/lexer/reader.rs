@@ -0,0 +1,142 @@
+//! Character source over an `io::BufRead`.
+
+use std::char::REPLACEMENT_CHARACTER;
+use std::io::{self, BufRead};
+
+use super::Input;
+
+/// `ReaderChars` decodes UTF-8 characters incrementally from a reader.
+///
+/// It yields each character along with its byte offset, like
+/// [`str::char_indices`]. Invalid UTF-8 sequences are yielded as
+/// [`REPLACEMENT_CHARACTER`] and a read error ends the input. Both are
+/// reported through [`Input::take_error`].
+#[derive(Debug)]
+pub struct ReaderChars<R> {
+    reader: R,
+    offset: usize,
+    done: bool,
+    peeked: Option<Option<(usize, char)>>,
+    error: Option<DecodeError>,
+}
+
+/// Error found while decoding the input of the lexer.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input holds an invalid UTF-8 sequence of `len` bytes.
+    InvalidUtf8 {
+        /// The length of the sequence in bytes.
+        len: usize,
+    },
+    /// The input could not be read.
+    Io(io::Error),
+}
+
+impl<R: BufRead> ReaderChars<R> {
+    /// Creates new character source from the reader.
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            offset: 0,
+            done: false,
+            peeked: None,
+            error: None,
+        }
+    }
+
+    /// Returns the next byte without consuming it.
+    fn peek_byte(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Consumes the next byte.
+    fn consume_byte(&mut self) {
+        self.reader.consume(1);
+        self.offset += 1;
+    }
+
+    /// Decodes the next character from the reader.
+    fn decode(&mut self) -> Option<(usize, char)> {
+        if self.done {
+            return None;
+        }
+
+        let position = self.offset;
+        let mut bytes = [0; 4];
+        let mut len = 0;
+        let mut expected = 1;
+
+        while len < expected {
+            let byte = match self.peek_byte() {
+                Ok(Some(byte)) => byte,
+                Ok(None) if len == 0 => return None,
+                Ok(None) => break,
+                Err(err) => {
+                    self.done = true;
+                    self.error = Some(DecodeError::Io(err));
+                    if len == 0 {
+                        return None;
+                    }
+                    break;
+                }
+            };
+            if len == 0 {
+                expected = sequence_len(byte);
+            } else if byte & 0xc0 != 0x80 {
+                break;
+            }
+            self.consume_byte();
+            bytes[len] = byte;
+            len += 1;
+        }
+
+        let ch = std::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next());
+        if ch.is_none() && self.error.is_none() {
+            self.error = Some(DecodeError::InvalidUtf8 { len });
+        }
+        Some((position, ch.unwrap_or(REPLACEMENT_CHARACTER)))
+    }
+}
+
+/// Returns the length of the UTF-8 sequence starting with the byte.
+const fn sequence_len(first: u8) -> usize {
+    match first {
+        0x00..=0x7f => 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => 1,
+    }
+}
+
+impl<R: BufRead> Iterator for ReaderChars<R> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.peeked.take().unwrap_or_else(|| self.decode())
+    }
+}
+
+impl<R: BufRead> Input for ReaderChars<R> {
+    fn peek(&mut self) -> Option<(usize, char)> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.decode());
+        }
+        self.peeked.flatten()
+    }
+
+    fn take_error(&mut self) -> Option<DecodeError> {
+        if self.peeked.is_some() {
+            return None;
+        }
+        self.error.take()
+    }
+}
@@ -13,5 +13,26 @@ mod expr;
 pub mod lexer;
 pub mod parser;
 pub mod repl;
-mod span;
+pub mod span;
 pub mod token;
+
+pub use ast::syntax::{
+    BlockStatement, ExprStatement, LocalVarDecl, ReturnStatement, TypeAnnotation,
+};
+pub use ast::{Program, Statement};
+pub use error::{Error, Warning};
+pub use expr::{ExprData, MatchArm, Pattern};
+pub use parser::parse_str;
+
+/// Returns the language version.
+pub const fn version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn version_is_crate_version() {
+        assert_eq!(super::version(), "0.1.0");
+    }
+}
@@ -4,27 +4,51 @@
 
 mod ops;
 
+use std::fmt;
+
 use crate::ast::syntax::*;
 use crate::ast::{Program, Statement};
-use crate::error::Error;
-use crate::expr::ExprData;
-use crate::lexer::Lexer;
+use crate::error::{Error, Warning};
+use crate::expr::{ExprData, MatchArm, Pattern};
+use crate::lexer::{Input, Lexer};
 use crate::token::{Token, TokenKind};
 
-use self::ops::Precedence;
+pub use self::ops::Precedence;
+
+/// Parser configuration.
+#[derive(Debug, Clone)]
+pub struct ParserConfig {
+    /// Continues parsing after an error to report as many errors as
+    /// possible, instead of stopping at the first one.
+    pub recover: bool,
+    /// Warns about statements that do not end with `;`.
+    pub strict_semicolons: bool,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            recover: true,
+            strict_semicolons: false,
+        }
+    }
+}
 
 /// Parser type.
 #[derive(Debug)]
-pub struct Parser<I: Iterator<Item = (usize, char)>> {
+pub struct Parser<I: Input> {
     lexer: Lexer<I>,
     current_token: Option<Token>,
     lookahead_token: Option<Token>,
     errors: Vec<Error>,
+    warnings: Vec<Warning>,
+    scopes: Vec<Vec<String>>,
+    config: ParserConfig,
 }
 
 impl<I> Parser<I>
 where
-    I: Iterator<Item = (usize, char)>,
+    I: Input,
 {
     /// Instantiates new parser.
     pub fn new(mut lexer: Lexer<I>) -> Self {
@@ -35,9 +59,23 @@ where
             current_token,
             lookahead_token,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            scopes: vec![Vec::new()],
+            config: ParserConfig::default(),
         }
     }
 
+    /// Sets the parser configuration.
+    pub const fn with_config(mut self, config: ParserConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns true if parsing must stop because of a previous error.
+    const fn must_stop(&self) -> bool {
+        !self.config.recover && (!self.errors.is_empty() || !self.lexer.errors.is_empty())
+    }
+
     /// Advances the parser to next tokens.
     fn advance(&mut self) {
         self.current_token = self.lookahead_token.take();
@@ -52,15 +90,59 @@ where
             if tok.kind.as_str().is_empty() {
                 break;
             }
-            if let Some(stmt) = self.parse_statement() {
-                statements.push(stmt);
+            match self.parse_statement() {
+                Some(stmt) => statements.push(stmt),
+                None => self.synchronize(),
+            }
+            if self.must_stop() {
+                break;
             }
             self.advance();
         }
+        self.merge_lexer_errors();
 
         Program { statements }
     }
 
+    /// Moves the errors reported by the lexer into the parser errors, in source order.
+    fn merge_lexer_errors(&mut self) {
+        self.errors.append(&mut self.lexer.errors);
+        self.errors.sort_by_key(|err| err.span().offset);
+    }
+
+    /// Returns the errors reported while parsing.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Returns the warnings reported while parsing.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Returns the summary of the parsed program.
+    pub fn summary(&self, program: &Program) -> ParseSummary {
+        ParseSummary::new(program, &self.errors, &self.warnings)
+    }
+
+    /// Declares the binding in the current scope.
+    ///
+    /// Reports a warning if the scope already declares the name.
+    fn declare(&mut self, name: &Token) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let ident = name.as_str().into_owned();
+        if scope.contains(&ident) {
+            self.warnings.push(Warning::ShadowedBinding {
+                name: ident,
+                span: name.span,
+            });
+        } else {
+            scope.push(ident);
+        }
+    }
+
     /// Returns true if the lookahead token as the expected type.
     fn is_valid_lookahead_token(&mut self, expected: TokenKind) -> bool {
         match &self.lookahead_token {
@@ -69,6 +151,7 @@ where
                     self.errors.push(Error::SyntaxError {
                         expected,
                         found: tok.kind,
+                        span: tok.span,
                     });
                 }
                 tok.kind == expected
@@ -85,6 +168,7 @@ where
                     self.errors.push(Error::SyntaxError {
                         expected,
                         found: tok.kind,
+                        span: tok.span,
                     });
                 }
                 tok.kind == expected
@@ -93,6 +177,37 @@ where
         }
     }
 
+    /// Returns true if the current token has the given kind.
+    fn current_token_is(&self, kind: TokenKind) -> bool {
+        matches!(&self.current_token, Some(tok) if tok.kind == kind)
+    }
+
+    /// Returns true if the lookahead token has the given kind.
+    fn lookahead_token_is(&self, kind: TokenKind) -> bool {
+        matches!(&self.lookahead_token, Some(tok) if tok.kind == kind)
+    }
+
+    /// Advances the parser and returns the new current token if the
+    /// lookahead token has the expected kind, otherwise records an error.
+    fn expect(&mut self, expected: TokenKind) -> Option<Token> {
+        self.advance_next_if(expected)?;
+        self.current_token.clone()
+    }
+
+    /// Advances the parser if the next token is an identifier and returns it.
+    ///
+    /// Reports a keyword found instead of the identifier as such.
+    fn expect_ident(&mut self) -> Option<Token> {
+        if let Some(tok) = self.lookahead_token.as_ref().filter(|tok| tok.is_keyword()) {
+            self.errors.push(Error::KeywordAsIdentifier {
+                keyword: tok.kind,
+                span: tok.span,
+            });
+            return None;
+        }
+        self.expect(TokenKind::Ident)
+    }
+
     /// Advances the parser if the next token is encountered.
     fn advance_next_if(&mut self, next: TokenKind) -> Option<()> {
         self.is_valid_lookahead_token(next).then(|| self.advance())
@@ -104,81 +219,546 @@ where
         match token.kind {
             TokenKind::Let => self.parse_var_decl(),
             TokenKind::Return => self.parse_return_statement(),
+            TokenKind::Lbrace => self.parse_block_statement(),
+            TokenKind::Function if self.lookahead_token_is(TokenKind::Ident) => {
+                self.parse_function_decl()
+            }
+            TokenKind::Else => {
+                self.errors.push(Error::ElseWithoutIf { span: token.span });
+                self.skip_else_branch();
+                None
+            }
             _ => self.parse_expr_statement(),
         }
     }
 
-    fn parse_var_decl(&mut self) -> Option<Statement> {
-        let token = self.current_token.take()?;
-        self.advance_next_if(TokenKind::Ident)?;
-        let name = self.current_token.take()?;
-        // TODO: skip expression parsing.
-        while !self.is_valid_current_token(TokenKind::Semi) {
+    /// Skips the braced branch of a stray `else`, if any.
+    fn skip_else_branch(&mut self) {
+        let mut depth = 0;
+        while let Some(tok) = &self.lookahead_token {
+            match tok.kind {
+                TokenKind::Eof => break,
+                TokenKind::Lbrace => depth += 1,
+                TokenKind::Rbrace => depth -= 1,
+                _ if depth == 0 => break,
+                _ => {}
+            }
+            self.advance();
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    /// Skips the rest of a statement that failed to parse.
+    ///
+    /// Stops on the `;` ending the statement or the `}` closing the enclosing
+    /// block, or before a token starting a new statement. Nested blocks are
+    /// skipped whole.
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+        while let Some(tok) = &self.current_token {
+            match tok.kind {
+                TokenKind::Eof => break,
+                TokenKind::Semi | TokenKind::Rbrace if depth == 0 => break,
+                TokenKind::Lbrace => depth += 1,
+                TokenKind::Rbrace => depth -= 1,
+                _ => {}
+            }
+            let next = self.lookahead_token.as_ref().map(|tok| tok.kind);
+            if depth == 0
+                && matches!(
+                    next,
+                    Some(TokenKind::Let | TokenKind::Return | TokenKind::Rbrace | TokenKind::Eof)
+                )
+            {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Parses a block statement.
+    fn parse_block_statement(&mut self) -> Option<Statement> {
+        self.parse_block().map(Statement::Block)
+    }
+
+    /// Parses a block of statements enclosed in braces.
+    ///
+    /// Reports a warning on the first statement that follows a `return`.
+    fn parse_block(&mut self) -> Option<BlockStatement> {
+        let token = self.current_token.clone()?;
+        let mut statements = Vec::new();
+        let mut returned = false;
+        self.advance();
+
+        self.scopes.push(Vec::new());
+        while let Some(tok) = &self.current_token {
+            if matches!(tok.kind, TokenKind::Rbrace | TokenKind::Eof) {
+                break;
+            }
+            let start = tok.span;
+            match self.parse_statement() {
+                Some(stmt) => {
+                    if returned {
+                        if let Some(end) = &self.current_token {
+                            let span = start.merge(end.span);
+                            self.warnings.push(Warning::UnreachableCode { span });
+                        }
+                        returned = false;
+                    }
+                    returned |= matches!(stmt, Statement::Return(_));
+                    statements.push(stmt);
+                }
+                None => {
+                    self.synchronize();
+                    if self.current_token_is(TokenKind::Rbrace) {
+                        break;
+                    }
+                }
+            }
+            if self.must_stop() {
+                break;
+            }
+            self.advance();
+        }
+        self.scopes.pop();
+        if !self.must_stop() {
+            self.is_valid_current_token(TokenKind::Rbrace);
+        }
+
+        Some(BlockStatement { token, statements })
+    }
+
+    /// Parses the branches of a conditional expression: `c ? a : b`.
+    ///
+    /// The operator is right associative and binds tighter than `|>`.
+    fn parse_conditional(&mut self, condition: ExprData) -> Option<ExprData> {
+        self.advance();
+        let consequence = self.parse_expression(Precedence::Lowest)?;
+        self.advance_next_if(TokenKind::Colon)?;
+        self.advance();
+        let alternative = self.parse_expression(Precedence::Pipe)?;
+        Some(ExprData::Conditional {
+            condition: Box::new(condition),
+            consequence: Box::new(consequence),
+            alternative: Box::new(alternative),
+        })
+    }
+
+    /// Parses a match expression: `match x { 1 => a, _ => b }`.
+    fn parse_match(&mut self) -> Option<ExprData> {
+        self.advance();
+        let scrutinee = self.parse_expression(Precedence::Lowest)?;
+        self.advance_next_if(TokenKind::Lbrace)?;
+
+        let mut arms = Vec::new();
+        while !self.lookahead_token_is(TokenKind::Rbrace) {
+            self.advance();
+            let pattern = self.parse_pattern()?;
+            self.advance_next_if(TokenKind::FatArrow)?;
+            self.advance();
+            let body = self.parse_expression(Precedence::Lowest)?;
+            arms.push(MatchArm { pattern, body });
+
+            if !self.lookahead_token_is(TokenKind::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        self.advance_next_if(TokenKind::Rbrace)?;
+
+        Some(ExprData::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+        })
+    }
+
+    /// Parses the pattern of a match arm.
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        let token = self.current_token.as_ref()?;
+        if token.kind == TokenKind::Ident && token.as_str() == "_" {
+            return Some(Pattern::Wildcard);
+        }
+
+        let start = token.span;
+        let expr = self.parse_expression(Precedence::Lowest)?;
+        match expr {
+            ExprData::Integer(_) | ExprData::Boolean(_) => Some(Pattern::Literal(expr)),
+            ExprData::Prefix {
+                operator: TokenKind::Minus,
+                ref right,
+            } if matches!(**right, ExprData::Integer(_)) => Some(Pattern::Literal(expr)),
+            _ => {
+                let end = self.current_token.as_ref().map_or(start, |tok| tok.span);
+                self.errors.push(Error::InvalidPattern {
+                    pattern: expr.to_string(),
+                    span: start.merge(end),
+                });
+                None
+            }
+        }
+    }
+
+    /// Parses a named function definition: `fn add(x, y) { x + y }`.
+    ///
+    /// The definition is desugared into a variable declaration of a function
+    /// literal.
+    fn parse_function_decl(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone()?;
+        self.advance();
+        let name = self.current_token.clone()?;
+        self.declare(&name);
+        let expr = self.parse_function_literal()?;
+        if self.lookahead_token_is(TokenKind::Semi) {
+            self.advance();
+        }
+        let end = self.current_token.clone()?;
+        Some(Statement::Var(LocalVarDecl {
+            token,
+            name,
+            annotation: None,
+            expr,
+            end,
+        }))
+    }
+
+    /// Parses the parameters and the body of a function literal.
+    fn parse_function_literal(&mut self) -> Option<ExprData> {
+        self.advance_next_if(TokenKind::Lparen)?;
+        let parameters = self.parse_function_parameters()?;
+        self.advance_next_if(TokenKind::Lbrace)?;
+        let body = self.parse_block()?;
+        Some(ExprData::Function { parameters, body })
+    }
+
+    /// Parses the comma separated list of function parameters.
+    fn parse_function_parameters(&mut self) -> Option<Vec<String>> {
+        let mut parameters = Vec::new();
+        if self.lookahead_token_is(TokenKind::Rparen) {
+            self.advance();
+            return Some(parameters);
+        }
+
+        loop {
+            let token = self.expect_ident()?;
+            let name = token.as_str().into_owned();
+            if parameters.contains(&name) {
+                self.errors.push(Error::DuplicateParameter {
+                    name: name.clone(),
+                    span: token.span,
+                });
+            }
+            parameters.push(name);
+
+            if !self.lookahead_token_is(TokenKind::Comma) {
+                break;
+            }
             self.advance();
         }
+        self.advance_next_if(TokenKind::Rparen)?;
+
+        Some(parameters)
+    }
+
+    /// Parses a variable declaration.
+    ///
+    /// The terminating `;` may be omitted at the end of input, so that
+    /// `let x = 5` typed at the REPL is still a valid binding.
+    fn parse_var_decl(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone()?;
+        let name = self.expect_ident()?;
+        let annotation = if self.lookahead_token_is(TokenKind::Colon) {
+            self.advance();
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+        self.declare(&name);
+        self.expect(TokenKind::Eq)?;
+        self.advance();
+        let expr = self.parse_expression(Precedence::Lowest)?;
+        self.expect_terminator()?;
+        let end = self.current_token.clone()?;
         let stmt = Statement::Var(LocalVarDecl {
             token,
             name,
-            expr: ExprData::VariableDecl(String::new()),
+            annotation,
+            expr,
+            end,
         });
 
         Some(stmt)
     }
 
-    fn parse_expr_statement(&self) -> Option<Statement> {
-        let stmt = ExprStatement {
-            token: self.current_token.clone().take()?,
-            expr: self.parse_expr(Precedence::Lower),
-        };
-        Some(Statement::Expr(stmt))
+    /// Advances the parser past the `;` ending a statement.
+    ///
+    /// The `;` may be left out before the end of input.
+    fn expect_terminator(&mut self) -> Option<()> {
+        if self.lookahead_token_is(TokenKind::Eof) {
+            self.lint_missing_semicolon();
+            return Some(());
+        }
+        self.advance_next_if(TokenKind::Semi)
     }
 
-    fn parse_expr(&self, precedence: Precedence) -> ExprData {
-        todo!()
+    /// Reports the missing `;` before the lookahead token if the strict
+    /// semicolon lint is enabled.
+    fn lint_missing_semicolon(&mut self) {
+        if !self.config.strict_semicolons {
+            return;
+        }
+        if let Some(tok) = &self.lookahead_token {
+            self.warnings
+                .push(Warning::MissingSemicolon { span: tok.span });
+        }
     }
 
-    fn parse_return_statement(&mut self) -> Option<Statement> {
-        let token = self.current_token.take()?;
-        if !self.is_valid_current_token(TokenKind::Semi) {
+    /// Parses the type named after the `:` of a `let` annotation.
+    fn parse_type_annotation(&mut self) -> Option<TypeAnnotation> {
+        self.advance();
+        let token = self.current_token.as_ref()?;
+        let annotation = TypeAnnotation::from_name(&token.as_str());
+        if annotation.is_none() {
+            self.errors.push(Error::UnknownTypeAnnotation {
+                name: token.as_str().into_owned(),
+                span: token.span,
+            });
+        }
+        annotation
+    }
+
+    fn parse_expr_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone()?;
+        let expr = self.parse_expression(Precedence::Lowest)?;
+        if self.lookahead_token_is(TokenKind::Semi) {
             self.advance();
+        } else if !self.current_token_is(TokenKind::Rbrace)
+            && !self.lookahead_token_is(TokenKind::Rbrace)
+        {
+            // Block-like expressions and the last expression of a block need no `;`.
+            self.lint_missing_semicolon();
         }
-        let stmt = Statement::Return(ReturnStatement {
-            token,
-            expr: ExprData::Return(String::new()),
-        });
-        Some(stmt)
+        Some(Statement::Expr(ExprStatement { token, expr }))
+    }
+
+    /// Parses an expression whose operators bind tighter than `precedence`.
+    ///
+    /// The parser must be positioned on the first token of the expression and
+    /// is left on its last token.
+    pub fn parse_expression(&mut self, precedence: Precedence) -> Option<ExprData> {
+        let expr = self.parse_operators(precedence);
+        self.merge_lexer_errors();
+        expr
+    }
+
+    /// Parses a prefix expression and the infix operators binding tighter
+    /// than `precedence`.
+    fn parse_operators(&mut self, precedence: Precedence) -> Option<ExprData> {
+        let mut left = self.parse_prefix()?;
+
+        while let Some(tok) = &self.lookahead_token {
+            if tok.kind == TokenKind::Semi || precedence >= tok.precedence() {
+                break;
+            }
+            self.advance();
+            left = self.parse_infix(left)?;
+        }
+
+        Some(left)
+    }
+
+    /// Parses an expression starting with the current token.
+    fn parse_prefix(&mut self) -> Option<ExprData> {
+        let token = self.current_token.as_ref()?;
+        match token.kind {
+            TokenKind::Ident => Some(ExprData::Ident(token.as_str().into_owned())),
+            TokenKind::Number => match token.as_str().parse() {
+                Ok(value) => Some(ExprData::Integer(value)),
+                Err(_) => {
+                    self.errors.push(Error::InvalidInteger {
+                        literal: token.as_str().into_owned(),
+                        span: token.span,
+                    });
+                    None
+                }
+            },
+            TokenKind::True | TokenKind::False => {
+                Some(ExprData::Boolean(token.kind == TokenKind::True))
+            }
+            TokenKind::Not | TokenKind::Minus => {
+                let operator = token.kind;
+                self.advance();
+                let right = self.parse_expression(Precedence::Prefix)?;
+                Some(ExprData::Prefix {
+                    operator,
+                    right: Box::new(right),
+                })
+            }
+            TokenKind::Lparen => self.parse_grouped(),
+            TokenKind::Function => self.parse_function_literal(),
+            TokenKind::Match => self.parse_match(),
+            kind => {
+                debug_assert!(
+                    !kind.is_literal_start(),
+                    "missing prefix parse for {kind:?}"
+                );
+                let offset = token.span.offset;
+                if token.value.operator_kind().is_some() || kind == TokenKind::Semi {
+                    self.errors.push(Error::ExpectedExpression {
+                        found: kind,
+                        span: token.span,
+                    });
+                } else if !self
+                    .lexer
+                    .errors
+                    .iter()
+                    .any(|err| err.span().offset == offset)
+                {
+                    self.errors.push(Error::NoPrefixParse {
+                        found: kind,
+                        span: token.span,
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Parses a parenthesized expression or sequence: `(a)` or `(a, b, c)`.
+    fn parse_grouped(&mut self) -> Option<ExprData> {
+        self.advance();
+        let mut exprs = vec![self.parse_expression(Precedence::Lowest)?];
+        while self.lookahead_token_is(TokenKind::Comma) {
+            self.advance();
+            self.advance();
+            exprs.push(self.parse_expression(Precedence::Lowest)?);
+        }
+        self.advance_next_if(TokenKind::Rparen)?;
+
+        if exprs.len() == 1 {
+            return exprs.pop();
+        }
+        Some(ExprData::Sequence(exprs))
+    }
+
+    /// Parses an infix expression whose operator is the current token.
+    fn parse_infix(&mut self, left: ExprData) -> Option<ExprData> {
+        let operator = self.current_token.as_ref()?.kind;
+        match operator {
+            TokenKind::Question => return self.parse_conditional(left),
+            TokenKind::Lparen => return self.parse_call(left),
+            _ => {}
+        }
+        self.advance();
+        let right = self.parse_expression(operator.precedence())?;
+        if operator == TokenKind::Pipe {
+            return Some(ExprData::Call {
+                function: Box::new(right),
+                arguments: vec![left],
+            });
+        }
+        Some(ExprData::Infix {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    /// Parses a call expression whose opening parenthesis is the current token.
+    fn parse_call(&mut self, function: ExprData) -> Option<ExprData> {
+        let mut arguments = Vec::new();
+        if self.lookahead_token_is(TokenKind::Rparen) {
+            self.advance();
+        } else {
+            loop {
+                self.advance();
+                arguments.push(self.parse_expression(Precedence::Lowest)?);
+
+                if !self.lookahead_token_is(TokenKind::Comma) {
+                    break;
+                }
+                self.advance();
+            }
+            self.advance_next_if(TokenKind::Rparen)?;
+        }
+
+        Some(ExprData::Call {
+            function: Box::new(function),
+            arguments,
+        })
+    }
+
+    /// Parses a return statement: `return x;` or a bare `return;`.
+    fn parse_return_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone()?;
+        let expr = if self.lookahead_token_is(TokenKind::Semi)
+            || self.lookahead_token_is(TokenKind::Eof)
+        {
+            None
+        } else {
+            self.advance();
+            Some(self.parse_expression(Precedence::Lowest)?)
+        };
+        self.expect_terminator()?;
+        Some(Statement::Return(ReturnStatement { token, expr }))
     }
 }
 
-/// `PrattParser` specifies the mechanism for parsing a token type
-/// using PRATTER PARSER.
-pub trait PrattParser {
-    /// Parse token if its found in the prefix position.
-    fn prefix_parse() -> ExprData;
-    /// Parse a token if its found in an infix position.
-    fn infix_parse(ast: ExprData) -> ExprData;
+/// Parses the source text and returns the program along with the parsing errors.
+///
+/// The program may be partial when errors are reported.
+pub fn parse_str(src: &str) -> (Program, Vec<Error>) {
+    let mut parser = Parser::new(Lexer::from_text(src));
+    let program = parser.parse();
+    (program, parser.errors)
 }
 
-impl PrattParser for TokenKind {
-    fn prefix_parse() -> ExprData {
-        todo!()
+/// Summary of a parsed program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseSummary {
+    /// The number of top-level statements parsed.
+    pub statement_count: usize,
+    /// The number of errors reported.
+    pub error_count: usize,
+    /// The number of warnings reported.
+    pub warning_count: usize,
+}
+
+impl ParseSummary {
+    /// Summarizes the program with the errors and warnings reported while parsing it.
+    pub const fn new(program: &Program, errors: &[Error], warnings: &[Warning]) -> Self {
+        Self {
+            statement_count: program.statements.len(),
+            error_count: errors.len(),
+            warning_count: warnings.len(),
+        }
     }
+}
 
-    fn infix_parse(ast: ExprData) -> ExprData {
-        todo!()
+impl fmt::Display for ParseSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parsed {} statements, {} errors",
+            self.statement_count, self.error_count
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ast::syntax::ExprStatement;
+    use crate::ast::syntax::{
+        BlockStatement, ExprStatement, LocalVarDecl, ReturnStatement, TypeAnnotation,
+    };
     use crate::ast::Statement;
-    use crate::error::Error;
-    use crate::expr::ExprData;
-    use crate::lexer::Lexer;
+    use crate::error::{Error, Warning};
+    use crate::expr::{ExprData, Pattern};
+    use crate::lexer::{Lexer, LexerConfig};
+    use crate::span;
     use crate::token::{TokenKind, TokenValue};
 
-    use super::Parser;
+    use super::{parse_str, ParseSummary, Parser, ParserConfig, Precedence};
 
     #[test]
     fn parse_var_decl() {
@@ -194,7 +774,7 @@ let foobar = 999999;
         assert_eq!(program.statements.len(), 3);
         check_parser_errors(&parser.errors);
 
-        let tests = vec!["x", "y", "foobar"];
+        let tests = ["x", "y", "foobar"];
 
         for (index, test) in tests.iter().enumerate() {
             let stmt = &program.statements[index];
@@ -202,6 +782,56 @@ let foobar = 999999;
         }
     }
 
+    #[test]
+    fn parse_var_decl_expr() {
+        let tests = [
+            ("let x = 5 + 3;", "let x = (5 + 3);"),
+            ("let y = foo(1);", "let y = foo(1);"),
+            ("let z = fn(a) { a };", "let z = fn(a) { a };"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (program, errors) = parse_str("let x = 5 + 3;");
+        check_parser_errors(&errors);
+        let Statement::Var(decl) = &program.statements[0] else {
+            panic!(
+                "expected variable declaration found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(
+            decl.expr,
+            ExprData::Infix {
+                left: int(5),
+                operator: TokenKind::Plus,
+                right: int(3),
+            }
+        );
+
+        let (_, errors) = parse_str("let x = 5 6;");
+        assert!(matches!(
+            errors[..],
+            [
+                Error::SyntaxError {
+                    expected: TokenKind::Semi,
+                    found: TokenKind::Number,
+                    ..
+                },
+                ..
+            ]
+        ));
+    }
+
+    #[test]
+    fn parse_var_decl_at_eof() {
+        let (program, errors) = parse_str("let x = 5");
+        check_parser_errors(&errors);
+        assert_eq!(program.statements.len(), 1);
+        check_vardecl_statement(&program.statements[0], "x");
+    }
+
     fn check_vardecl_statement(statement: &Statement, name: &str) {
         let Statement::Var(decl) = statement else { panic!("expected variable declaration found: {:?}", statement)};
 
@@ -220,6 +850,14 @@ let foobar = 999999;
         panic!("error: could not compile due to previous error");
     }
 
+    /// Asserts that `source` parses without errors and displays as `expected`.
+    #[track_caller]
+    fn assert_parses_to(source: &str, expected: &str) {
+        let (program, errors) = parse_str(source);
+        check_parser_errors(&errors);
+        assert_eq!(program.to_string(), expected, "{source}");
+    }
+
     #[test]
     fn parse_return_stmt() {
         let input = r#"
@@ -237,6 +875,37 @@ return 999999;
         for stmt in &program.statements {
             assert!(matches!(stmt, Statement::Return(_)))
         }
+
+        let tests = [
+            ("return 5 + 5;", "return (5 + 5);"),
+            ("return;", "return;"),
+            ("return", "return;"),
+            ("return add(1, 2)", "return add(1, 2);"),
+            ("fn() { return; }", "fn() { return; }"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (program, errors) = parse_str("return 5 + 5;");
+        check_parser_errors(&errors);
+        assert!(matches!(
+            &program.statements[0],
+            Statement::Return(ReturnStatement {
+                expr: Some(ExprData::Infix {
+                    operator: TokenKind::Plus,
+                    ..
+                }),
+                ..
+            })
+        ));
+
+        let (program, errors) = parse_str("return;");
+        check_parser_errors(&errors);
+        assert!(matches!(
+            &program.statements[0],
+            Statement::Return(ReturnStatement { expr: None, .. })
+        ));
     }
 
     #[test]
@@ -256,7 +925,7 @@ return 999999;
         let (token, expr) = match &program.statements[0] {
             Statement::Expr(ExprStatement {
                 token,
-                expr: ExprData::ExprStatement(expr),
+                expr: ExprData::Ident(expr),
                 ..
             }) => (token, expr),
             _ => panic!(
@@ -269,4 +938,795 @@ return 999999;
         assert_eq!(expr, "foobar");
         assert_eq!(token.value, TokenValue::Word("foobar".into()));
     }
+
+    fn int(value: i64) -> Box<ExprData> {
+        Box::new(ExprData::Integer(value))
+    }
+
+    #[test]
+    fn parse_prefix_and_infix_expr() {
+        let tests = [
+            (
+                "-15;",
+                ExprData::Prefix {
+                    operator: TokenKind::Minus,
+                    right: int(15),
+                },
+            ),
+            (
+                "!true;",
+                ExprData::Prefix {
+                    operator: TokenKind::Not,
+                    right: Box::new(ExprData::Boolean(true)),
+                },
+            ),
+            (
+                "5 - 2 - 1;",
+                ExprData::Infix {
+                    left: Box::new(ExprData::Infix {
+                        left: int(5),
+                        operator: TokenKind::Minus,
+                        right: int(2),
+                    }),
+                    operator: TokenKind::Minus,
+                    right: int(1),
+                },
+            ),
+            (
+                "(1 + 2) * 3 == x",
+                ExprData::Infix {
+                    left: Box::new(ExprData::Infix {
+                        left: Box::new(ExprData::Infix {
+                            left: int(1),
+                            operator: TokenKind::Plus,
+                            right: int(2),
+                        }),
+                        operator: TokenKind::Star,
+                        right: int(3),
+                    }),
+                    operator: TokenKind::EqEq,
+                    right: Box::new(ExprData::Ident("x".into())),
+                },
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let (program, errors) = parse_str(input);
+            check_parser_errors(&errors);
+            assert_eq!(program.statements.len(), 1, "{input}");
+            let Statement::Expr(ExprStatement { expr, .. }) = &program.statements[0] else {
+                panic!(
+                    "expected expression statement found {:?}",
+                    &program.statements[0]
+                );
+            };
+            assert_eq!(&expected, expr, "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_expression_at_precedence() {
+        let lexer = Lexer::from_text("1 + 2 * 3");
+        let mut parser = Parser::new(lexer);
+        let expr = parser.parse_expression(Precedence::Lowest);
+        check_parser_errors(&parser.errors);
+        assert_eq!(
+            expr,
+            Some(ExprData::Infix {
+                left: int(1),
+                operator: TokenKind::Plus,
+                right: Box::new(ExprData::Infix {
+                    left: int(2),
+                    operator: TokenKind::Star,
+                    right: int(3),
+                }),
+            })
+        );
+
+        let lexer = Lexer::from_text("1 + 2 * 3");
+        let mut parser = Parser::new(lexer);
+        let expr = parser.parse_expression(Precedence::Sum);
+        assert_eq!(expr, Some(ExprData::Integer(1)));
+    }
+
+    #[test]
+    fn parse_expr_without_prefix() {
+        let (program, errors) = parse_str(")");
+        assert!(program.statements.is_empty());
+        assert!(matches!(
+            errors[..],
+            [Error::NoPrefixParse {
+                found: TokenKind::Rparen,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn parse_str_returns_program_and_errors() {
+        let (program, errors) = parse_str("");
+        assert!(program.statements.is_empty());
+        check_parser_errors(&errors);
+
+        let (program, errors) = parse_str("let");
+        assert!(program.statements.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            Error::SyntaxError {
+                expected: TokenKind::Ident,
+                found: TokenKind::Eof,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_else_without_if() {
+        let (program, errors) = parse_str("else { 1 }");
+        assert!(program.statements.is_empty(), "{program:?}");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], Error::ElseWithoutIf { .. }));
+        assert_eq!(errors[0].to_string(), "'else' without matching 'if'");
+
+        let (program, errors) = parse_str("let x = 1; else { { 2 } } let y = 3;");
+        assert_eq!(program.statements.len(), 2, "{program:?}");
+        assert!(matches!(errors[..], [Error::ElseWithoutIf { .. }]));
+    }
+
+    #[test]
+    fn parse_block_statement() {
+        let (program, errors) = parse_str("{ { } }");
+        check_parser_errors(&errors);
+        assert_eq!(program.statements.len(), 1);
+
+        let Statement::Block(BlockStatement { token, statements }) = &program.statements[0] else {
+            panic!(
+                "expected block statement found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(TokenKind::Lbrace, token.kind);
+        assert_eq!(statements.len(), 1);
+        assert!(
+            matches!(&statements[0], Statement::Block(BlockStatement { statements, .. }) if statements.is_empty())
+        );
+
+        let (program, errors) = parse_str("{ let x = 1; } x;");
+        check_parser_errors(&errors);
+        assert_eq!(program.statements.len(), 2);
+        assert!(matches!(
+            &program.statements[0],
+            Statement::Block(BlockStatement { statements, .. })
+                if matches!(statements[..], [Statement::Var(_)])
+        ));
+
+        let (_, errors) = parse_str("{");
+        assert!(matches!(
+            errors[..],
+            [Error::SyntaxError {
+                expected: TokenKind::Rbrace,
+                found: TokenKind::Eof,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn parse_function_literal() {
+        let (program, errors) = parse_str("fn(x, y) { x + y; }");
+        check_parser_errors(&errors);
+        assert_eq!(program.statements.len(), 1);
+
+        let Statement::Expr(ExprStatement {
+            expr: ExprData::Function { parameters, body },
+            ..
+        }) = &program.statements[0]
+        else {
+            panic!(
+                "expected function literal found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(parameters, &["x", "y"]);
+        assert_eq!(body.statements.len(), 1);
+
+        let (_, errors) = parse_str("fn() {}");
+        check_parser_errors(&errors);
+    }
+
+    #[test]
+    fn parse_named_function_decl() {
+        let (program, errors) = parse_str("fn add(x, y) { x + y } add;");
+        check_parser_errors(&errors);
+        assert_eq!(program.statements.len(), 2);
+
+        let Statement::Var(LocalVarDecl {
+            token,
+            name,
+            expr: ExprData::Function { parameters, body },
+            ..
+        }) = &program.statements[0]
+        else {
+            panic!(
+                "expected variable declaration found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(TokenKind::Function, token.kind);
+        assert_eq!(name.as_str(), "add");
+        assert_eq!(parameters, &["x", "y"]);
+        let Statement::Expr(ExprStatement { expr, .. }) = &body.statements[0] else {
+            panic!(
+                "expected expression statement found {:?}",
+                &body.statements[0]
+            );
+        };
+        assert_eq!(
+            expr,
+            &ExprData::Infix {
+                left: Box::new(ExprData::Ident("x".into())),
+                operator: TokenKind::Plus,
+                right: Box::new(ExprData::Ident("y".into())),
+            }
+        );
+
+        let (_, errors) = parse_str("fn 5() {}");
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn expect_returns_consumed_token() {
+        let lexer = Lexer::from_text("let x 5");
+        let mut parser = Parser::new(lexer);
+
+        let token = parser
+            .expect(TokenKind::Ident)
+            .expect("expected identifier");
+        assert_eq!(token.value, TokenValue::Word("x".into()));
+        check_parser_errors(&parser.errors);
+
+        assert!(parser.expect(TokenKind::Eq).is_none());
+        assert!(matches!(
+            parser.errors[..],
+            [Error::SyntaxError {
+                expected: TokenKind::Eq,
+                found: TokenKind::Number,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn parse_duplicate_function_parameters() {
+        let (_, errors) = parse_str("fn(x, y, x) { x }");
+        assert_eq!(errors.len(), 1);
+        let Error::DuplicateParameter { name, span } = &errors[0] else {
+            panic!("expected duplicate parameter error found {:?}", errors[0]);
+        };
+        assert_eq!(name, "x");
+        assert_eq!(span.column_pos, 9);
+        assert_eq!(errors[0].to_string(), "duplicate parameter name: x");
+
+        let (_, errors) = parse_str("fn(x, y) { x }");
+        check_parser_errors(&errors);
+    }
+
+    #[test]
+    fn parse_illegal_characters_lenient_and_strict() {
+        let input = "let x = 1; @ 2; $ 3;";
+
+        let lexer = Lexer::from_text(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse();
+        assert_eq!(program.statements.len(), 1);
+        assert!(matches!(
+            parser.errors[..],
+            [
+                Error::NoPrefixParse {
+                    found: TokenKind::Unknown,
+                    ..
+                },
+                Error::NoPrefixParse {
+                    found: TokenKind::Unknown,
+                    ..
+                }
+            ]
+        ));
+
+        let config = LexerConfig {
+            strict: true,
+            ..LexerConfig::default()
+        };
+        let lexer = Lexer::from_text(input).with_config(config.clone());
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse();
+        assert_eq!(program.statements.len(), 1);
+        assert_eq!(parser.errors.len(), 1);
+        assert!(matches!(
+            parser.errors[0],
+            Error::IllegalCharacter { ch: '@', .. }
+        ));
+        assert_eq!(
+            parser.errors[0].to_string(),
+            "illegal character '@' at 1:11"
+        );
+
+        let lexer = Lexer::from_text("1 + @").with_config(config);
+        let mut parser = Parser::new(lexer);
+        parser.parse_expression(Precedence::Lowest);
+        assert!(matches!(
+            parser.errors[..],
+            [Error::IllegalCharacter { ch: '@', .. }]
+        ));
+    }
+
+    #[test]
+    fn parse_conditional_expr() {
+        let tests = [
+            ("true ? 1 : 2", "(true ? 1 : 2)"),
+            ("false ? 1 : 2", "(false ? 1 : 2)"),
+            ("a < b ? a + 1 : b * 2", "((a < b) ? (a + 1) : (b * 2))"),
+            ("a ? b : c ? d : e", "(a ? b : (c ? d : e))"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (_, errors) = parse_str("true ? 1 2");
+        assert!(matches!(
+            errors[0],
+            Error::SyntaxError {
+                expected: TokenKind::Colon,
+                found: TokenKind::Number,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_not_keyword() {
+        let tests = [
+            ("not true", "(!true)"),
+            ("not (1 < 2)", "(!(1 < 2))"),
+            ("not a == !b", "((!a) == (!b))"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+    }
+
+    #[test]
+    fn parse_match_expr() {
+        let tests = [
+            (
+                "match x { 1 => a, -2 => b, _ => c }",
+                "match x { 1 => a, (-2) => b, _ => c }",
+            ),
+            (
+                "match x + 1 { true => 1 + 2, }",
+                "match (x + 1) { true => (1 + 2) }",
+            ),
+            ("match x {}", "match x {  }"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (program, errors) = parse_str("match x { 1 => a, _ => c }");
+        check_parser_errors(&errors);
+        let Statement::Expr(ExprStatement {
+            expr: ExprData::Match { arms, .. },
+            ..
+        }) = &program.statements[0]
+        else {
+            panic!(
+                "expected match expression found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(arms[0].pattern, Pattern::Literal(ExprData::Integer(1)));
+        assert_eq!(arms[1].pattern, Pattern::Wildcard);
+
+        let (_, errors) = parse_str("match x { y => 1 }");
+        assert!(
+            matches!(&errors[..], [Error::InvalidPattern { pattern, .. }, ..] if pattern == "y"),
+            "{errors:?}"
+        );
+        let span = errors[0].span();
+        assert_eq!(span::snippet("match x { y => 1 }", &span), Some("y"));
+    }
+
+    #[test]
+    fn parse_call_expr() {
+        let tests = [
+            ("add", "add"),
+            ("add()", "add()"),
+            ("add(1, 2 * 3, -x)", "add(1, (2 * 3), (-x))"),
+            ("a + add(b)(c) * d", "(a + (add(b)(c) * d))"),
+            ("fn(x) { x }(5)", "fn(x) { x }(5)"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (program, _) = parse_str("add()");
+        let Statement::Expr(ExprStatement {
+            expr: ExprData::Call { arguments, .. },
+            ..
+        }) = &program.statements[0]
+        else {
+            panic!(
+                "expected call expression found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert!(arguments.is_empty());
+
+        let (program, _) = parse_str("add");
+        assert!(matches!(
+            &program.statements[0],
+            Statement::Expr(ExprStatement {
+                expr: ExprData::Ident(name),
+                ..
+            }) if name == "add"
+        ));
+    }
+
+    #[test]
+    fn summarize_parsed_program() {
+        let (program, errors) = parse_str("let x = 5; else; 1 + 2;");
+        let summary = ParseSummary::new(&program, &errors, &[]);
+        assert_eq!(
+            summary,
+            ParseSummary {
+                statement_count: 2,
+                error_count: 1,
+                warning_count: 0,
+            }
+        );
+        assert_eq!(summary.to_string(), "parsed 2 statements, 1 errors");
+    }
+
+    #[test]
+    fn report_lexer_errors_once_in_source_order() {
+        let (_, errors) = parse_str("let x = 123abc;");
+        assert!(
+            matches!(&errors[..], [Error::InvalidNumericLiteral { literal, .. }] if literal == "123abc"),
+            "{errors:?}"
+        );
+
+        let (_, errors) = parse_str("let x = 1abc; else { 2 }");
+        assert!(
+            matches!(
+                errors[..],
+                [
+                    Error::InvalidNumericLiteral { .. },
+                    Error::ElseWithoutIf { .. }
+                ]
+            ),
+            "{errors:?}"
+        );
+        let offsets: Vec<usize> = errors.iter().map(|err| err.span().offset).collect();
+        assert_eq!(offsets, [8, 14]);
+    }
+
+    #[test]
+    fn let_statement_span() {
+        let tests = [
+            ("let x = 1 + 2;", 14),
+            ("  let x = 1 + 2", 13),
+            ("fn id(x) { x };", 15),
+        ];
+        for (input, len) in tests {
+            let (program, errors) = parse_str(input);
+            check_parser_errors(&errors);
+            let Statement::Var(decl) = &program.statements[0] else {
+                panic!(
+                    "expected variable declaration found {:?}",
+                    &program.statements[0]
+                );
+            };
+            let span = decl.span();
+            assert_eq!(span.column_pos, input.len() - input.trim_start().len());
+            assert_eq!(span.len, len, "{input}");
+            assert_eq!(span::snippet(input, &span), Some(input.trim()));
+        }
+
+        let (left, _) = parse_str("let x = 1;");
+        let (right, _) = parse_str("\n  let x = 1;");
+        assert_eq!(left.statements, right.statements);
+    }
+
+    #[test]
+    fn parse_keyword_as_identifier() {
+        let tests = [
+            ("let if = 1;", TokenKind::If),
+            ("let let = 5;", TokenKind::Let),
+            ("fn(x, true) { x }", TokenKind::True),
+        ];
+        for (input, expected) in tests {
+            let (_, errors) = parse_str(input);
+            assert!(
+                matches!(
+                    errors[..],
+                    [Error::KeywordAsIdentifier { keyword, .. }, ..] if keyword == expected
+                ),
+                "{input}: {errors:?}"
+            );
+        }
+
+        let (_, errors) = parse_str("let if = 1;");
+        assert_eq!(
+            errors[0].to_string(),
+            "expected identifier, found keyword 'if'"
+        );
+    }
+
+    #[test]
+    fn parse_not_with_comparison() {
+        let tests = [
+            ("!1 < 2", "((!1) < 2)"),
+            ("!(1 < 2)", "(!(1 < 2))"),
+            ("not x == y", "((!x) == y)"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+    }
+
+    #[test]
+    fn parse_operator_precedence() {
+        let tests = [
+            ("-a * b", "((-a) * b)"),
+            ("a + b + c", "((a + b) + c)"),
+            ("a + b * c + d / e - f", "(((a + (b * c)) + (d / e)) - f)"),
+            ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
+            (
+                "3 + 4 * 5 == 3 * 1 + 4 * 5",
+                "((3 + (4 * 5)) == ((3 * 1) + (4 * 5)))",
+            ),
+            ("-(5 + 5)", "(-(5 + 5))"),
+            ("a * (b + c) * d", "((a * (b + c)) * d)"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+    }
+
+    #[test]
+    fn parse_pipe_expr() {
+        let tests = [
+            ("5 |> double", "double(5)"),
+            ("x |> f |> g", "g(f(x))"),
+            ("1 + 2 |> add(3)", "add(3)((1 + 2))"),
+            ("c ? a : b |> f", "f((c ? a : b))"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+    }
+
+    #[test]
+    fn parse_sequence_expr() {
+        let tests = [
+            ("(1, 2, 3)", "(1, 2, 3)"),
+            ("(1)", "1"),
+            ("(a, b + c) * 2", "((a, (b + c)) * 2)"),
+            ("f(1, 2, 3)", "f(1, 2, 3)"),
+            ("f((1, 2), 3)", "f((1, 2), 3)"),
+        ];
+        for (input, expected) in tests {
+            assert_parses_to(input, expected);
+        }
+
+        let (program, _) = parse_str("f(1, 2, 3)");
+        let Statement::Expr(ExprStatement {
+            expr: ExprData::Call { arguments, .. },
+            ..
+        }) = &program.statements[0]
+        else {
+            panic!(
+                "expected call expression found {:?}",
+                &program.statements[0]
+            );
+        };
+        assert_eq!(arguments.len(), 3);
+    }
+
+    #[test]
+    fn parse_var_decl_annotation() {
+        let tests = [
+            (
+                "let x: int = 5;",
+                "let x: int = 5;",
+                Some(TypeAnnotation::Int),
+            ),
+            (
+                "let b: bool = true;",
+                "let b: bool = true;",
+                Some(TypeAnnotation::Bool),
+            ),
+            (
+                "let f: fn = fn(a) { a };",
+                "let f: fn = fn(a) { a };",
+                Some(TypeAnnotation::Function),
+            ),
+            ("let y = 5;", "let y = 5;", None),
+        ];
+        for (input, expected, annotation) in tests {
+            assert_parses_to(input, expected);
+            let (program, _) = parse_str(input);
+            let Statement::Var(decl) = &program.statements[0] else {
+                panic!(
+                    "expected variable declaration found {:?}",
+                    &program.statements[0]
+                );
+            };
+            assert_eq!(decl.annotation, annotation, "{input}");
+        }
+
+        let (program, errors) = parse_str("let x: float = 1; let y = 2;");
+        assert_eq!(program.to_string(), "let y = 2;");
+        assert!(
+            matches!(&errors[..], [Error::UnknownTypeAnnotation { name, .. }] if name == "float"),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn parse_misplaced_operator() {
+        let tests = [("5 + * 3", TokenKind::Star, 4), ("* 5", TokenKind::Star, 0)];
+        for (input, kind, column_pos) in tests {
+            let (_, errors) = parse_str(input);
+            let [Error::ExpectedExpression { found, span }] = &errors[..] else {
+                panic!("expected a misplaced operator error found {errors:?}");
+            };
+            assert_eq!(*found, kind);
+            assert_eq!(span.column_pos, column_pos);
+            assert_eq!(errors[0].to_string(), "expected expression, found '*'");
+        }
+    }
+
+    #[test]
+    fn report_shadowed_bindings() {
+        let source = "let x = 1; let y = 2; let x = 3; { let x = 4; let y = 5; }";
+        let mut parser = Parser::new(Lexer::from_text(source));
+        let program = parser.parse();
+        check_parser_errors(&parser.errors);
+        assert_eq!(program.statements.len(), 4);
+
+        let [Warning::ShadowedBinding { name, span }] = parser.warnings() else {
+            panic!(
+                "expected one shadowing warning found {:?}",
+                parser.warnings()
+            );
+        };
+        assert_eq!(name, "x");
+        assert_eq!(span.column_pos, 26);
+        assert_eq!(
+            parser.summary(&program),
+            ParseSummary {
+                statement_count: 4,
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn report_unreachable_code() {
+        let source = "fn(){ return 1; 2; 3; }";
+        let mut parser = Parser::new(Lexer::from_text(source));
+        parser.parse();
+        check_parser_errors(&parser.errors);
+
+        let [Warning::UnreachableCode { span }] = parser.warnings() else {
+            panic!(
+                "expected one unreachable code warning found {:?}",
+                parser.warnings()
+            );
+        };
+        assert_eq!(&source[span.offset..span.offset + span.len], "2;");
+    }
+
+    #[test]
+    fn lint_missing_semicolons() {
+        let source = "fn(x) { x }\n{ x }\nx + 1\nlet y = 1";
+        let config = ParserConfig {
+            strict_semicolons: true,
+            ..ParserConfig::default()
+        };
+        let mut parser = Parser::new(Lexer::from_text(source)).with_config(config);
+        let program = parser.parse();
+        check_parser_errors(&parser.errors);
+        assert_eq!(program.statements.len(), 4);
+
+        let spans: Vec<_> = parser
+            .warnings()
+            .iter()
+            .map(|warning| {
+                assert!(matches!(warning, Warning::MissingSemicolon { .. }));
+                warning.span().to_string()
+            })
+            .collect();
+        assert_eq!(spans, ["4:0", "4:9"]);
+
+        let mut parser = Parser::new(Lexer::from_text("let x = 1"));
+        parser.parse();
+        check_parser_errors(&parser.errors);
+        assert!(parser.warnings().is_empty());
+    }
+
+    #[test]
+    fn recover_after_errors() {
+        let source = "let = 1; let y 2; fn(x) { x + ; }; let z = 3;";
+
+        let mut parser = Parser::new(Lexer::from_text(source));
+        let program = parser.parse();
+        assert!(
+            matches!(
+                parser.errors(),
+                [
+                    Error::SyntaxError {
+                        expected: TokenKind::Ident,
+                        found: TokenKind::Eq,
+                        ..
+                    },
+                    Error::SyntaxError {
+                        expected: TokenKind::Eq,
+                        found: TokenKind::Number,
+                        ..
+                    },
+                    Error::ExpectedExpression {
+                        found: TokenKind::Semi,
+                        ..
+                    },
+                ]
+            ),
+            "{:?}",
+            parser.errors()
+        );
+        let statements: Vec<String> = program.statements.iter().map(|s| s.to_string()).collect();
+        assert_eq!(statements, ["fn(x) { }", "let z = 3;"]);
+
+        let tests = [
+            ("let = 1; let y = 2;", "let y = 2;"),
+            ("let x = ; let y = 2;", "let y = 2;"),
+            ("let x = (1 + ) * 2; let y = 2;", "let y = 2;"),
+            ("{ 1 + } let y = 2;", "{ }let y = 2;"),
+            (
+                "let f = fn(x) { x + }; let y = 2;",
+                "let f = fn(x) { };let y = 2;",
+            ),
+        ];
+        for (input, expected) in tests {
+            let (program, errors) = parse_str(input);
+            assert_eq!(errors.len(), 1, "{input}: {errors:?}");
+            assert_eq!(program.to_string(), expected, "{input}");
+        }
+
+        let mut parser = Parser::new(Lexer::from_text(source)).with_config(ParserConfig {
+            recover: false,
+            ..ParserConfig::default()
+        });
+        let program = parser.parse();
+        assert!(matches!(
+            parser.errors(),
+            [Error::SyntaxError {
+                expected: TokenKind::Ident,
+                found: TokenKind::Eq,
+                ..
+            }]
+        ));
+        assert!(program.statements.is_empty());
+
+        let mut parser =
+            Parser::new(Lexer::from_text("{ 1 + ; 2 + ; }")).with_config(ParserConfig {
+                recover: false,
+                ..ParserConfig::default()
+            });
+        parser.parse();
+        assert_eq!(parser.errors().len(), 1);
+    }
 }
@@ -1,7 +1,62 @@
-/// Operator precedences.
+//! Operator precedences.
 
-#[derive(Debug, Clone)]
-/// Defines operators precedences.
+use crate::token::{Token, TokenKind};
+
+/// Defines operators precedences, from the lowest to the highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Precedence {
-    Lower,
+    /// The lowest precedence.
+    Lowest,
+    /// The pipe operator: `x |> f`.
+    Pipe,
+    /// The conditional operator: `c ? a : b`.
+    Conditional,
+    /// Equality operators: `==` and `!=`.
+    Equals,
+    /// Comparison operators: `<` and `>`.
+    LessGreater,
+    /// Additive operators: `+` and `-`.
+    Sum,
+    /// Multiplicative operators: `*` and `/`.
+    Product,
+    /// Prefix operators: `-x` and `!x`.
+    Prefix,
+    /// Call expressions: `add(x)`.
+    Call,
+}
+
+impl TokenKind {
+    /// Returns the precedence of the token kind in an infix position.
+    pub const fn precedence(&self) -> Precedence {
+        match self {
+            Self::Pipe => Precedence::Pipe,
+            Self::Question => Precedence::Conditional,
+            Self::EqEq | Self::Ne => Precedence::Equals,
+            Self::Lt | Self::Gt => Precedence::LessGreater,
+            Self::Plus | Self::Minus => Precedence::Sum,
+            Self::Star | Self::Slash => Precedence::Product,
+            Self::Lparen => Precedence::Call,
+            _ => Precedence::Lowest,
+        }
+    }
+}
+
+impl Token {
+    /// Returns the precedence of the token in an infix position.
+    pub const fn precedence(&self) -> Precedence {
+        self.kind.precedence()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::Span;
+    use crate::token::TokenValue;
+
+    #[test]
+    fn token_precedence_delegates_to_kind() {
+        let token = Token::new(TokenValue::Operator("+"), TokenKind::Plus, Span::new(1, 0));
+        assert_eq!(token.precedence(), Precedence::Sum);
+    }
 }
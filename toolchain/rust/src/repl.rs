@@ -2,25 +2,136 @@
 
 use std::io;
 
+use rustyline::error::ReadlineError;
 use rustyline::{Editor, Result};
 
 use crate::lexer::Lexer;
+use crate::parser::{Parser, ParserConfig};
 use crate::token::TokenValue;
 
 const PROMPT: &str = "> ";
 
+/// `LineSource` provides the input lines of the REPL.
+trait LineSource {
+    /// Reads a line of input.
+    fn readline(&mut self, prompt: &str) -> Result<String>;
+}
+
+impl LineSource for Editor<()> {
+    fn readline(&mut self, prompt: &str) -> Result<String> {
+        Editor::readline(self, prompt)
+    }
+}
+
 /// Starts the REPL.
-pub fn start(mut writer: impl io::Write) -> Result<()> {
+///
+/// `Ctrl-C` cancels the current line and `Ctrl-D` exits the loop.
+pub fn start(writer: impl io::Write) -> Result<()> {
     let mut line_reader = Editor::<()>::new()?;
-    while let Ok(line) = line_reader.readline(PROMPT) {
-        let mut lexer = Lexer::from_text(&line);
-        while let Some(tok) = lexer.next_token() {
-            if tok.value == TokenValue::Eof {
+    run(&mut line_reader, writer)
+}
+
+/// Runs the REPL loop over the lines of the source.
+fn run(source: &mut impl LineSource, mut writer: impl io::Write) -> Result<()> {
+    loop {
+        let line = match source.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                writeln!(&mut writer, "error: {err}")?;
                 break;
             }
-            write!(&mut writer, "{:?}", tok).expect("failed to write");
+        };
+
+        let output = eval_line(&line);
+        if !output.is_empty() {
+            write!(&mut writer, "{output}")?;
             writer.flush()?;
         }
     }
     Ok(())
 }
+
+/// Returns the output of the REPL for the input line.
+///
+/// A blank line produces no output. The line is parsed without error
+/// recovery, so at most the first syntax error is reported.
+fn eval_line(line: &str) -> String {
+    if line.trim().is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    let mut lexer = Lexer::from_text(line);
+    while let Some(tok) = lexer.next_token() {
+        if tok.value == TokenValue::Eof {
+            break;
+        }
+        output.push_str(&format!("{tok:?}"));
+    }
+
+    let config = ParserConfig {
+        recover: false,
+        ..ParserConfig::default()
+    };
+    let mut parser = Parser::new(Lexer::from_text(line)).with_config(config);
+    parser.parse();
+    for err in parser.errors() {
+        output.push_str(&format!("\nerror: {err}"));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use rustyline::error::ReadlineError;
+    use rustyline::Result;
+
+    use super::{eval_line, run, LineSource};
+
+    /// A line source replaying predefined inputs.
+    struct MockSource(VecDeque<Result<String>>);
+
+    impl LineSource for MockSource {
+        fn readline(&mut self, _prompt: &str) -> Result<String> {
+            self.0.pop_front().unwrap_or(Err(ReadlineError::Eof))
+        }
+    }
+
+    #[test]
+    fn interrupt_continues_and_eof_exits() {
+        let mut source = MockSource(VecDeque::from([
+            Ok("first".to_string()),
+            Err(ReadlineError::Interrupted),
+            Ok("second".to_string()),
+            Err(ReadlineError::Eof),
+            Ok("third".to_string()),
+        ]));
+        let mut output = Vec::new();
+        run(&mut source, &mut output).expect("repl failed");
+
+        let output = String::from_utf8(output).expect("invalid output");
+        assert!(output.contains("first"));
+        assert!(output.contains("second"));
+        assert!(!output.contains("third"));
+        assert_eq!(source.0.len(), 1);
+    }
+
+    #[test]
+    fn blank_lines_produce_no_output() {
+        assert_eq!(eval_line(""), "");
+        assert_eq!(eval_line("   "), "");
+        assert!(eval_line(" let ").contains("Let"));
+    }
+
+    #[test]
+    fn report_first_syntax_error() {
+        let output = eval_line("let = 1; let = 2;");
+        assert_eq!(output.matches("error:").count(), 1, "{output}");
+        assert!(output.ends_with("\nerror: expected 'ident', found '=' at 1:4"));
+        assert!(!eval_line("let x = 1;").contains("error:"));
+    }
+}
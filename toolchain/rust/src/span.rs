@@ -1,18 +1,201 @@
 //! Span type.
 
-#[derive(Debug, Clone)]
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// The Span data represents a region of code associated with an input token.
 pub struct Span {
     /// The line number for this token.
     pub lineno: usize,
     /// The column number where this token was found.
+    ///
+    /// Columns are counted in characters from the start of the line, starting at 0.
     pub column_pos: usize,
+    /// The byte offset of the region in the input.
+    pub offset: usize,
+    /// The length of the region in bytes.
+    pub len: usize,
 }
 
 impl Span {
     /// Creates new span.
     #[inline]
     pub const fn new(lineno: usize, column_pos: usize) -> Self {
-        Self { lineno, column_pos }
+        Self {
+            lineno,
+            column_pos,
+            offset: 0,
+            len: 0,
+        }
+    }
+
+    /// Sets the byte offset of the region in the input.
+    #[inline]
+    pub const fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the length of the region in bytes.
+    #[inline]
+    pub const fn with_len(mut self, len: usize) -> Self {
+        self.len = len;
+        self
+    }
+
+    /// Returns the span covering from the start of `self` to the end of `end`.
+    ///
+    /// The merged span keeps the line and column of `self`.
+    pub const fn merge(self, end: Self) -> Self {
+        let len = (end.offset + end.len).saturating_sub(self.offset);
+        self.with_len(len)
+    }
+
+    /// Creates new span covering the byte offsets `start..end` of the source
+    /// indexed by `index`.
+    ///
+    /// Returns `None` if the offsets are out of the source bounds.
+    pub fn from_offsets(index: &LineIndex<'_>, start: usize, end: usize) -> Option<Self> {
+        let (lineno, column) = index.line_col(start)?;
+        index.line_col(end)?;
+        let span = Self::new(lineno, column).with_offset(start);
+        Some(span.with_len(end.checked_sub(start)?))
+    }
+
+    /// Returns the span location formatted as `line:col`.
+    pub fn line_col(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.lineno, self.column_pos)
+    }
+}
+
+/// `LineIndex` maps byte offsets of a source text to line and column numbers.
+///
+/// Lines start at 1 and columns, counted in characters, start at 0.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Creates new line index for the source text.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the line and column of the byte offset.
+    ///
+    /// Returns `None` if the offset is out of bounds or not on a character
+    /// boundary.
+    pub fn line_col(&self, offset: usize) -> Option<(usize, usize)> {
+        if !self.source.is_char_boundary(offset) {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.source[line_start..offset].chars().count();
+        Some((line, column))
+    }
+
+    /// Returns the byte offset of the line and column.
+    ///
+    /// Returns `None` if the position is past the end of its line.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        let line_start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let line_end = self
+            .line_starts
+            .get(line)
+            .map_or(self.source.len(), |next| next - 1);
+        let text = &self.source[line_start..line_end];
+        text.char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(text.len()))
+            .nth(column)
+            .map(|offset| line_start + offset)
+    }
+}
+
+/// Returns the source text covered by the span.
+///
+/// Returns `None` if the span is out of the source bounds.
+pub fn snippet<'a>(source: &'a str, span: &Span) -> Option<&'a str> {
+    source.get(span.offset..span.offset.checked_add(span.len)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snippet, LineIndex, Span};
+    use crate::lexer::Lexer;
+
+    #[test]
+    fn format_span_as_line_col() {
+        let span = Span::new(3, 7);
+        assert_eq!(span.line_col(), "3:7");
+        assert_eq!(span.to_string(), "3:7");
+    }
+
+    #[test]
+    fn merge_spans() {
+        let start = Span::new(1, 4).with_offset(4).with_len(3);
+        let end = Span::new(1, 12).with_offset(12).with_len(1);
+        assert_eq!(start.merge(end), start.with_len(9));
+    }
+
+    #[test]
+    fn extract_snippet() {
+        let source = "let x = 1;\nlet foobar = 5;";
+        let mut lexer = Lexer::from_text(source);
+        for _ in 0..6 {
+            lexer.next_token().expect("failed to create lexeme");
+        }
+        let token = lexer.next_token().expect("failed to create lexeme");
+        assert_eq!(snippet(source, &token.span), Some("foobar"));
+
+        let span = Span::new(1, 12).with_offset(12).with_len(20);
+        assert_eq!(snippet(source, &span), None);
+        assert_eq!(snippet(source, &Span::new(1, 0).with_offset(42)), None);
+    }
+
+    #[test]
+    fn map_offsets_to_line_col() {
+        let source = "let a = 1;\nlet é = 2;\n";
+        let index = LineIndex::new(source);
+
+        assert_eq!(index.line_col(0), Some((1, 0)));
+        assert_eq!(index.line_col(10), Some((1, 10)));
+        assert_eq!(index.line_col(11), Some((2, 0)));
+        assert_eq!(index.line_col(18), Some((2, 6)));
+        assert_eq!(index.line_col(23), Some((3, 0)));
+        assert_eq!(index.line_col(16), None);
+        assert_eq!(index.line_col(42), None);
+
+        assert_eq!(index.offset(1, 0), Some(0));
+        assert_eq!(index.offset(2, 0), Some(11));
+        assert_eq!(index.offset(2, 6), Some(18));
+        assert_eq!(index.offset(2, 10), Some(22));
+        assert_eq!(index.offset(2, 11), None);
+        assert_eq!(index.offset(0, 0), None);
+
+        let span = Span::from_offsets(&index, 15, 17).expect("offsets in bounds");
+        assert_eq!(span, Span::new(2, 4).with_offset(15).with_len(2));
+        assert_eq!(snippet(source, &span), Some("é"));
+        assert_eq!(Span::from_offsets(&index, 17, 15), None);
+
+        let index = LineIndex::new("ab\ncd");
+        let span = Span::from_offsets(&index, 4, 5).expect("offsets in bounds");
+        assert_eq!(span.to_string(), "2:1");
+        assert_eq!(span.offset, 4);
     }
 }
@@ -5,11 +5,12 @@
 mod kind;
 
 use std::borrow::Cow;
+use std::fmt;
 
-pub(super) use self::kind::TokenKind;
+pub use self::kind::TokenKind;
 use super::span::Span;
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 /// Token type.
 pub struct Token {
     /// Token value.
@@ -40,16 +41,32 @@ pub enum TokenValue {
     /// A semicolon token: `;`
     Semi,
 
+    /// A colon token: `:`
+    Colon,
+
     /// A number. Only integers are currently supported.
     Number(String),
 
     /// An operator: `_`, `*`, ...
-    Operator(String),
+    Operator(&'static str),
 
     /// End of file,
     Eof,
 }
 
+impl TokenValue {
+    /// Returns the kind of the operator stored in the value, if any.
+    pub fn operator_kind(&self) -> Option<TokenKind> {
+        match self {
+            Self::Operator(op) => match TokenKind::from(*op) {
+                TokenKind::Unknown => None,
+                kind => Some(kind),
+            },
+            _ => None,
+        }
+    }
+}
+
 impl Token {
     /// Creates new token.
     pub const fn new(value: TokenValue, kind: TokenKind, span: Span) -> Self {
@@ -60,12 +77,100 @@ impl Token {
     pub fn as_str(&self) -> Cow<'_, str> {
         match &self.value {
             TokenValue::Unknown(c) | TokenValue::Delimiter(c) => Cow::from(c.to_string()),
-            TokenValue::Word(s) | TokenValue::Operator(s) | TokenValue::Number(s) => Cow::from(s),
+            TokenValue::Word(s) | TokenValue::Number(s) => Cow::from(s),
+            TokenValue::Operator(s) => Cow::from(*s),
             TokenValue::Comma => Cow::from(","),
             TokenValue::Semi => Cow::from(";"),
+            TokenValue::Colon => Cow::from(":"),
             TokenValue::Eof => Cow::from(""),
         }
     }
+
+    /// Returns true if the token is a keyword.
+    pub const fn is_keyword(&self) -> bool {
+        matches!(self.value, TokenValue::Word(_)) && self.kind.is_keyword()
+    }
+
+    /// Sets the kind of a word token to the keyword it spells, if any.
+    ///
+    /// The word itself is preserved in the token value.
+    pub fn reclassify_keywords(&mut self) {
+        if let TokenValue::Word(word) = &self.value {
+            self.kind = TokenKind::keyword(word).unwrap_or(TokenKind::Ident);
+        }
+    }
+
+    /// Returns the number of characters in the token source text.
+    pub fn literal_len(&self) -> usize {
+        self.as_str().chars().count()
+    }
+}
+
+impl TokenKind {
+    /// Returns the keyword kind spelled by the word, if any.
+    pub fn keyword(word: &str) -> Option<Self> {
+        let kind = match word {
+            "let" => Self::Let,
+            "true" => Self::True,
+            "false" => Self::False,
+            "if" => Self::If,
+            "else" => Self::Else,
+            "return" => Self::Return,
+            "fn" => Self::Function,
+            "not" => Self::Not,
+            "match" => Self::Match,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Returns true if the kind is a keyword.
+    ///
+    /// `Not` is spelled both as the `!` operator and as the `not` keyword.
+    pub const fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            Self::Let
+                | Self::True
+                | Self::False
+                | Self::If
+                | Self::Else
+                | Self::Return
+                | Self::Function
+                | Self::Not
+                | Self::Match
+        )
+    }
+
+    /// Returns true if a token of this kind can begin an expression.
+    ///
+    /// These are the kinds the parser has a prefix parse function for. `if`
+    /// expressions, array and hash literals and strings cannot be parsed
+    /// yet, so `If`, `Lbracket` and `Lbrace` are not included.
+    pub const fn is_literal_start(&self) -> bool {
+        matches!(
+            self,
+            Self::Ident
+                | Self::Number
+                | Self::True
+                | Self::False
+                | Self::Not
+                | Self::Minus
+                | Self::Lparen
+                | Self::Function
+                | Self::Match
+        )
+    }
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Token {{ {:?}, {:?} @ {} }}",
+            self.kind, self.value, self.span
+        )
+    }
 }
 
 impl PartialEq for Token {
@@ -75,3 +180,99 @@ impl PartialEq for Token {
 }
 
 impl Eq for Token {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Token, TokenKind, TokenValue};
+    use crate::span::Span;
+
+    #[test]
+    fn token_literal_len() {
+        let semi = Token::new(TokenValue::Semi, TokenKind::Semi, Span::new(1, 0));
+        assert_eq!(semi.literal_len(), 1);
+
+        let ne = Token::new(TokenValue::Operator("!="), TokenKind::Ne, Span::new(1, 0));
+        assert_eq!(ne.literal_len(), 2);
+
+        let ident = Token::new(
+            TokenValue::Word("café".into()),
+            TokenKind::Ident,
+            Span::new(1, 0),
+        );
+        assert_eq!(ident.literal_len(), 4);
+
+        let eof = Token::new(TokenValue::Eof, TokenKind::Eof, Span::new(1, 0));
+        assert_eq!(eof.literal_len(), 0);
+    }
+
+    #[test]
+    fn reclassify_keyword_tokens() {
+        let mut token = Token::new(
+            TokenValue::Word("fn".into()),
+            TokenKind::Ident,
+            Span::new(1, 0),
+        );
+        assert!(!token.is_keyword());
+        token.reclassify_keywords();
+        assert_eq!(TokenKind::Function, token.kind);
+        assert_eq!(TokenValue::Word("fn".into()), token.value);
+        assert!(token.is_keyword());
+
+        let mut token = Token::new(
+            TokenValue::Word("fun".into()),
+            TokenKind::Function,
+            Span::new(1, 0),
+        );
+        token.reclassify_keywords();
+        assert_eq!(TokenKind::Ident, token.kind);
+        assert!(!token.is_keyword());
+    }
+
+    #[test]
+    fn keyword_predicates_agree() {
+        for word in ["let", "true", "false", "if", "else", "return", "fn", "not"] {
+            let kind = TokenKind::keyword(word).expect("word should be a keyword");
+            assert!(kind.is_keyword(), "{word}");
+            let token = Token::new(TokenValue::Word(word.into()), kind, Span::new(1, 0));
+            assert!(token.is_keyword(), "{word}");
+        }
+    }
+
+    #[test]
+    fn kind_is_literal_start() {
+        for kind in [
+            TokenKind::Ident,
+            TokenKind::Number,
+            TokenKind::True,
+            TokenKind::False,
+            TokenKind::Not,
+            TokenKind::Minus,
+            TokenKind::Lparen,
+            TokenKind::Function,
+            TokenKind::Match,
+        ] {
+            assert!(kind.is_literal_start(), "{kind:?}");
+        }
+        for kind in [
+            TokenKind::Let,
+            TokenKind::If,
+            TokenKind::Lbrace,
+            TokenKind::Plus,
+            TokenKind::Rparen,
+            TokenKind::Semi,
+            TokenKind::Eof,
+        ] {
+            assert!(!kind.is_literal_start(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn compact_debug_format() {
+        let token = Token::new(
+            TokenValue::Word("x".into()),
+            TokenKind::Ident,
+            Span::new(2, 4).with_len(1),
+        );
+        assert_eq!(format!("{token:?}"), r#"Token { Ident, Word("x") @ 2:4 }"#);
+    }
+}
@@ -8,17 +8,21 @@ macro_rules! define_token_kind {
         #[derive(Clone, Debug, Copy, PartialEq, Eq)]
         #[doc = concat!(stringify!($kind_name), " type. See module level [documentation](self)")]
         pub enum $kind_name {
-            $($name,)*
-           Unknown,
+            $(
+                #[doc = concat!("The `", stringify!($name), "` token.")]
+                $name,
+            )*
+            /// An unknown token.
+            Unknown,
         }
 
         impl $kind_name {
             #[doc = concat!("A slice of literal values for each ", stringify!($kind_name))]
-            const LITERALS: &'static[&'static str] = &[$($literal,)*];
+            const LITERALS: &'static[&'static str] = &[$($literal,)* "unknown"];
 
             #[doc = concat!("Returns a string slice representing the literal value of the ",
                             stringify!($kind_name))]
-            pub const fn as_str(&self) -> &str {
+            pub const fn as_str(&self) -> &'static str {
                 Self::LITERALS[*self as usize]
             }
 
@@ -54,6 +58,7 @@ define_token_kind! [
     Else => "else",
     Return => "return",
     Function => "fn",
+    Match => "match",
     Ident => "ident",
     Plus => "+",
     Minus => "-",
@@ -63,8 +68,15 @@ define_token_kind! [
     Eq => "=",
     EqEq => "==",
     Ne => "!=",
+    FatArrow => "=>",
     Lt => "<",
     Gt => ">",
+    Dot => ".",
+    DotDot => "..",
+    DotDotEq => "..=",
+    Bar => "|",
+    OrOr => "||",
+    Pipe => "|>",
     Lparen => "(",
     Rparen => ")",
     Lbrace => "{",
@@ -73,6 +85,8 @@ define_token_kind! [
     Rbracket => "]",
     Comma => ",",
     Semi => ";",
+    Colon => ":",
+    Question => "?",
     Number => "number",
     Eof => "",
 